@@ -0,0 +1,11 @@
+use open_editor::{EditorCallBuilder, errors::OpenEditorError};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), OpenEditorError> {
+    let template = "Hello, {name}!\nWelcome to {place}.";
+    // Also works:
+    // let filled_template = open_editor::edit_string_async(template).await?;
+    let filled_template = EditorCallBuilder::new().edit_string_async(template).await?;
+    println!("Filled Template:\n{filled_template}");
+    Ok(())
+}
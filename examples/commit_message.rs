@@ -0,0 +1,16 @@
+use open_editor::{CleanupMode, EditorCallBuilder, errors::OpenEditorError};
+
+fn main() -> Result<(), OpenEditorError> {
+    // Editors recognize this file name and apply git commit syntax highlighting.
+    let message = EditorCallBuilder::new()
+        .with_temp_file_name("COMMIT_EDITMSG")
+        .with_help_text(
+            "Please enter the commit message for your changes. Lines starting\n\
+             with '#' will be ignored, and an empty message aborts the commit.",
+        )
+        .with_cleanup(CleanupMode::Strip)
+        .abort_on_empty(true)
+        .open_editor()?;
+    println!("Commit message:\n{message}");
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use open_editor::{EditorCallBuilder, errors::OpenEditorError};
+
+fn main() -> Result<(), OpenEditorError> {
+    // Reads `$XDG_CONFIG_HOME/open-editor/config.toml` if it exists, for example:
+    //
+    // editor = "nvim"
+    // prefer = "terminal"
+    //
+    // [extensions]
+    // md = "typora"
+    let user_input = EditorCallBuilder::new()
+        .with_config_file()?
+        .with_temp_file_suffix(".md")
+        .edit_string("# Notes\n")?;
+    println!("User input:\n{user_input}");
+    Ok(())
+}
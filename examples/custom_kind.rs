@@ -0,0 +1,17 @@
+use open_editor::{ArgTemplate, Editor, EditorCallBuilder, EditorKind, errors::OpenEditorError};
+
+fn main() -> Result<(), OpenEditorError> {
+    // An editor this crate doesn't know, which takes `--goto file:line:column`
+    // and `--wait` to block until the file is closed.
+    let template = ArgTemplate::new(&["{wait}", "--goto", "{file}:{line}:{column}"])
+        .with_wait_args(&["--wait"]);
+    let editor = Editor::new(EditorKind::Custom(template), "my-editor".into());
+
+    let user_input = EditorCallBuilder::new()
+        .with_editor(editor)
+        .at_line(2)
+        .at_column(5)
+        .edit_string("First line\nSecond line")?;
+    println!("User input:\n{user_input}");
+    Ok(())
+}
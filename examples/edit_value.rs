@@ -0,0 +1,21 @@
+use open_editor::{Format, edit_value, errors::OpenEditorError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Settings {
+    name: String,
+    retries: u32,
+    verbose: bool,
+}
+
+fn main() -> Result<(), OpenEditorError> {
+    let settings = Settings {
+        name: String::from("example"),
+        retries: 3,
+        verbose: false,
+    };
+    // Parse errors reopen the editor with the error above the content.
+    let settings = edit_value(&settings, Format::Toml)?;
+    println!("New settings: {settings:?}");
+    Ok(())
+}
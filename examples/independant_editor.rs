@@ -1,4 +1,4 @@
-use std::{fs, path::PathBuf, str::FromStr, thread::sleep, time::Duration};
+use std::{path::PathBuf, str::FromStr, thread::sleep, time::Duration};
 
 use open_editor::EditorCallBuilder;
 
@@ -8,17 +8,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let filename = PathBuf::from_str("./test")?;
 
     // Spawn editor without waiting
-    EditorCallBuilder::new()
-        .wait_for_editor(false)
-        .open_file(&filename)?;
+    let mut handle = EditorCallBuilder::new().spawn_file(&filename)?;
 
-    println!("Editor launched. Press Ctrl+C to stop.\n");
+    println!("Editor launched. Close it or press Ctrl+C to stop.\n");
 
-    loop {
-        let contents = fs::read_to_string(&filename).unwrap_or_default();
+    while handle.try_wait()?.is_none() {
+        let contents = handle.read_contents().unwrap_or_default();
 
         println!("--- Content of {} ---\n{}\n", filename.display(), contents);
 
         sleep(Duration::from_secs(1));
     }
+    Ok(())
 }
@@ -0,0 +1,22 @@
+use open_editor::{EditorCallBuilder, ValidationError, errors::OpenEditorError};
+
+/// Parses one number per line, reporting the line of the first invalid one.
+fn parse_numbers(text: &str) -> Result<Vec<i64>, ValidationError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim().parse().map_err(|e| {
+                ValidationError::new(format!("invalid number `{}`: {e}", line.trim()))
+                    .at_line(i + 1)
+            })
+        })
+        .collect()
+}
+
+fn main() -> Result<(), OpenEditorError> {
+    // The editor is reopened until every line is a number, or the file is emptied.
+    let numbers = EditorCallBuilder::new().edit_until_valid("1\n2\nthree\n", parse_numbers)?;
+    println!("Sum: {}", numbers.iter().sum::<i64>());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use crate::editor_profile::{EditorInterface, EditorProfile};
+
+/// Arguments of an editor unknown to this crate, used with [`EditorKind::Custom`](crate::EditorKind::Custom).
+///
+/// Each argument may contain the `{file}`, `{line}` and `{column}` placeholders. An argument
+/// that is exactly `{wait}` is replaced by the wait arguments when waiting for the editor, or
+/// by the no-wait arguments otherwise. If no argument contains `{file}`, the file path is
+/// appended at the end.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArgTemplate {
+    args: Vec<String>,
+    wait_args: Vec<String>,
+    no_wait_args: Vec<String>,
+    interface: EditorInterface,
+}
+
+impl ArgTemplate {
+    /// Creates a new [`ArgTemplate`] from arguments containing placeholders.
+    #[must_use]
+    pub fn new(args: &[&str]) -> Self {
+        Self {
+            args: args.iter().map(|&s| s.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+    /// Sets the arguments replacing `{wait}` when waiting for the editor.
+    #[must_use]
+    pub fn with_wait_args(self, wait_args: &[&str]) -> Self {
+        Self {
+            wait_args: wait_args.iter().map(|&s| s.to_string()).collect(),
+            ..self
+        }
+    }
+    /// Sets the arguments replacing `{wait}` when not waiting for the editor.
+    #[must_use]
+    pub fn with_no_wait_args(self, no_wait_args: &[&str]) -> Self {
+        Self {
+            no_wait_args: no_wait_args.iter().map(|&s| s.to_string()).collect(),
+            ..self
+        }
+    }
+    /// Sets where the editor displays its interface.
+    #[must_use]
+    pub fn with_interface(self, interface: EditorInterface) -> Self {
+        Self { interface, ..self }
+    }
+    /// Expands the template for opening a file at a specific line and column.
+    /// `{wait}` is removed if `wait` is `None`.
+    fn expand(
+        &self,
+        file_path: &Path,
+        wait: Option<bool>,
+        line: usize,
+        column: usize,
+    ) -> Vec<String> {
+        let path = file_path.to_string_lossy();
+        let values = [
+            ("{file}", path.to_string()),
+            ("{line}", line.to_string()),
+            ("{column}", column.to_string()),
+        ];
+        let mut args = vec![];
+        for arg in &self.args {
+            if arg == "{wait}" {
+                match wait {
+                    Some(true) => args.extend(self.wait_args.iter().cloned()),
+                    Some(false) => args.extend(self.no_wait_args.iter().cloned()),
+                    None => {}
+                }
+            } else {
+                args.push(substitute(arg, &values));
+            }
+        }
+        if !self.args.iter().any(|arg| arg.contains("{file}")) {
+            args.push(path.into_owned());
+        }
+        args
+    }
+}
+impl EditorProfile for ArgTemplate {
+    fn file_args(&self, file_path: &Path, line: usize, column: usize) -> Vec<String> {
+        self.expand(file_path, None, line, column)
+    }
+    fn interface(&self) -> EditorInterface {
+        self.interface
+    }
+    fn editor_args(&self, file_path: &Path, wait: bool, line: usize, column: usize) -> Vec<String> {
+        self.expand(file_path, Some(wait), line, column)
+    }
+}
+
+/// Replaces the placeholders of `arg` by their values in a single pass, so that placeholders
+/// appearing in a value, such as a file named `a{line}.txt`, are kept as they are.
+fn substitute(arg: &str, values: &[(&str, String)]) -> String {
+    let mut result = String::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                result.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                result.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_in_file_path_are_kept() {
+        let template = ArgTemplate::new(&["--open={file}:{line}:{column}", "{{line}}"]);
+        assert_eq!(
+            template.file_args(Path::new("/tmp/a{line}b{column}.txt"), 7, 3),
+            ["--open=/tmp/a{line}b{column}.txt:7:3", "{7}"]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    fs::OpenOptions,
+    path::{Path, PathBuf},
+};
+
+use tempfile::{NamedTempFile, TempDir};
+
+use crate::errors::OpenEditorError;
+
+/// The file used to edit strings in the editor.
+///
+/// Temporary files are removed when dropped, even if the editor call fails.
+#[derive(Debug)]
+pub(crate) enum BackingFile {
+    /// A temporary file with a random name, created exclusively with owner-only permissions.
+    Random(NamedTempFile),
+    /// A file with a fixed name inside a private temporary directory.
+    Named { dir: TempDir, path: PathBuf },
+    /// A file chosen by the user, only removed if it did not exist before.
+    Given { path: PathBuf, created: bool },
+}
+
+impl BackingFile {
+    /// Creates a temporary file with a random name made of `prefix`, random characters and `suffix`.
+    pub(crate) fn random(prefix: &str, suffix: &str) -> Result<Self, OpenEditorError> {
+        tempfile::Builder::new()
+            .prefix(prefix)
+            .suffix(suffix)
+            .tempfile()
+            .map(BackingFile::Random)
+            .map_err(OpenEditorError::file_manipulation(&std::env::temp_dir()))
+    }
+    /// Creates a file named `file_name` inside a private temporary directory whose name starts with `prefix`.
+    ///
+    /// The name must be a single path component, so that the file cannot end up outside the directory.
+    pub(crate) fn named(prefix: &str, file_name: &str) -> Result<Self, OpenEditorError> {
+        if Path::new(file_name).file_name() != Some(file_name.as_ref()) {
+            return Err(OpenEditorError::FileManipulationFail {
+                file_path: PathBuf::from(file_name),
+                error: std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "the file name must be a single path component",
+                ),
+            });
+        }
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(prefix);
+        #[cfg(unix)]
+        {
+            use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+            builder.permissions(Permissions::from_mode(0o700));
+        }
+        let dir = builder
+            .tempdir()
+            .map_err(OpenEditorError::file_manipulation(&std::env::temp_dir()))?;
+        let path = dir.path().join(file_name);
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&path)
+            .map_err(OpenEditorError::file_manipulation(&path))?;
+        Ok(BackingFile::Named { dir, path })
+    }
+    /// Uses the file chosen by the user, remembering whether it already exists.
+    pub(crate) fn given(path: &Path) -> Self {
+        BackingFile::Given {
+            path: path.to_path_buf(),
+            created: !path.exists(),
+        }
+    }
+    /// Gets the path of the file.
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            BackingFile::Random(file) => file.path(),
+            BackingFile::Named { dir: _, path } | BackingFile::Given { path, created: _ } => path,
+        }
+    }
+    /// Writes `content` to the file, replacing what was in it.
+    pub(crate) fn write(&self, content: &str) -> Result<(), OpenEditorError> {
+        std::fs::write(self.path(), content)
+            .map_err(OpenEditorError::file_manipulation(self.path()))
+    }
+    /// Removes the file, reporting any failure. A file chosen by the user that existed before
+    /// is left in place.
+    pub(crate) fn close(self) -> Result<(), OpenEditorError> {
+        let file_path = self.path().to_path_buf();
+        match self {
+            BackingFile::Random(file) => file.close(),
+            BackingFile::Named { dir, path: _ } => dir.close(),
+            BackingFile::Given {
+                path,
+                created: true,
+            } => std::fs::remove_file(path),
+            BackingFile::Given {
+                path: _,
+                created: false,
+            } => Ok(()),
+        }
+        .map_err(|error| OpenEditorError::TempFileCleanupFail { file_path, error })
+    }
+    /// Keeps the file on disk.
+    pub(crate) fn keep(self) -> Result<(), OpenEditorError> {
+        match self {
+            BackingFile::Random(file) => {
+                file.keep()
+                    .map(|_| ())
+                    .map_err(|e| OpenEditorError::FileManipulationFail {
+                        file_path: e.file.path().to_path_buf(),
+                        error: e.error,
+                    })
+            }
+            BackingFile::Named { dir, path: _ } => {
+                let _ = dir.keep();
+                Ok(())
+            }
+            BackingFile::Given { .. } => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+/// How the text written in the editor is cleaned up, like git's `commit.cleanup`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupMode {
+    /// Removes comment lines, trailing whitespace, leading and trailing empty lines, and
+    /// collapses consecutive empty lines.
+    Strip,
+    /// Same as `Strip` but keeps comment lines.
+    Whitespace,
+    /// Same as `Whitespace`, and removes everything from the scissors line on:
+    /// `# ------------------------ >8 ------------------------`.
+    Scissors,
+    /// Leaves the text as it is.
+    #[default]
+    Verbatim,
+}
+
+impl CleanupMode {
+    /// Cleans up `text`, where comment lines start with `comment_prefix`.
+    ///
+    /// The result ends with a newline unless it is empty, except in `Verbatim` mode.
+    #[must_use]
+    pub fn apply(self, text: &str, comment_prefix: &str) -> String {
+        let text = match self {
+            CleanupMode::Verbatim => return text.to_string(),
+            CleanupMode::Scissors => cut_at_scissors(text, comment_prefix),
+            CleanupMode::Strip | CleanupMode::Whitespace => text,
+        };
+        let strip_comments = self == CleanupMode::Strip && !comment_prefix.is_empty();
+        let mut result = String::new();
+        let mut pending_empty_line = false;
+        for line in text.lines() {
+            if strip_comments && line.starts_with(comment_prefix) {
+                continue;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                // Leading empty lines are dropped, others are written before the next line
+                pending_empty_line = !result.is_empty();
+                continue;
+            }
+            if pending_empty_line {
+                result.push('\n');
+                pending_empty_line = false;
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+        result
+    }
+}
+
+/// Gets the line after which everything is removed in `Scissors` mode.
+fn scissors_line(comment_prefix: &str) -> String {
+    format!("{comment_prefix} ------------------------ >8 ------------------------")
+}
+
+/// Removes the scissors line and everything after it.
+fn cut_at_scissors<'a>(text: &'a str, comment_prefix: &str) -> &'a str {
+    let scissors = scissors_line(comment_prefix);
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim_end() == scissors {
+            return &text[..start];
+        }
+        start += line.len();
+    }
+    text
+}
+
+/// How the string edited by `edit_string` is presented and cleaned up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Cleanup {
+    pub(crate) mode: CleanupMode,
+    pub(crate) comment_prefix: String,
+    pub(crate) help_text: Option<String>,
+}
+impl Default for Cleanup {
+    fn default() -> Self {
+        Self {
+            mode: CleanupMode::Verbatim,
+            comment_prefix: String::from("#"),
+            help_text: None,
+        }
+    }
+}
+impl Cleanup {
+    /// Appends the commented help text to the string to edit, after an empty line.
+    pub(crate) fn prepare(&self, content: &str) -> String {
+        let Some(help_text) = &self.help_text else {
+            return content.to_string();
+        };
+        let mut text = content.to_string();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push('\n');
+        if self.mode == CleanupMode::Scissors {
+            text.push_str(&scissors_line(&self.comment_prefix));
+            text.push('\n');
+            text.push_str(&self.comment("Do not modify or remove the line above."));
+            text.push_str(&self.comment("Everything below it will be ignored."));
+        }
+        for line in help_text.lines() {
+            text.push_str(&self.comment(line));
+        }
+        text
+    }
+    /// Cleans up the edited string.
+    pub(crate) fn apply(&self, content: &str) -> String {
+        self.mode.apply(content, &self.comment_prefix)
+    }
+    /// Turns a line into a comment line.
+    pub(crate) fn comment(&self, line: &str) -> String {
+        if line.is_empty() {
+            format!("{}\n", self.comment_prefix)
+        } else {
+            format!("{} {line}\n", self.comment_prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+    #[test]
+    fn strip_removes_comments_and_extra_blank_lines() {
+        let text = "\n\n# comment\nsubject  \n\n\n\nbody\t\n# other\n\n\n";
+        assert_eq!(CleanupMode::Strip.apply(text, "#"), "subject\n\nbody\n");
+    }
+
+    #[test]
+    fn strip_only_removes_lines_starting_with_the_prefix() {
+        assert_eq!(CleanupMode::Strip.apply("a #b\n#c\n", "#"), "a #b\n");
+    }
+
+    #[test]
+    fn whitespace_keeps_comments() {
+        let text = "\n# comment\nsubject  \n\n\nbody";
+        assert_eq!(
+            CleanupMode::Whitespace.apply(text, "#"),
+            "# comment\nsubject\n\nbody\n"
+        );
+    }
+
+    #[test]
+    fn only_blank_lines_give_an_empty_string() {
+        for mode in [
+            CleanupMode::Strip,
+            CleanupMode::Whitespace,
+            CleanupMode::Scissors,
+        ] {
+            assert_eq!(mode.apply("\n  \n\t\n", "#"), "", "{mode:?}");
+        }
+        assert_eq!(CleanupMode::Strip.apply("# only\n", "#"), "");
+    }
+
+    #[test]
+    fn scissors_cuts_at_the_scissors_line() {
+        let text = format!("subject\n# kept\n\n{SCISSORS}\ndiff --git a b\n");
+        assert_eq!(CleanupMode::Scissors.apply(&text, "#"), "subject\n# kept\n");
+        // Without the scissors line, it behaves like `Whitespace`
+        assert_eq!(
+            CleanupMode::Scissors.apply("a  \n\n\n# b\n", "#"),
+            "a\n\n# b\n"
+        );
+        // Only a line that is exactly the scissors line cuts
+        let indented = format!("a\n {SCISSORS}\nb\n");
+        assert_eq!(CleanupMode::Scissors.apply(&indented, "#"), indented);
+    }
+
+    #[test]
+    fn verbatim_keeps_everything() {
+        let text = "\n# comment  \n\n\nbody";
+        assert_eq!(CleanupMode::Verbatim.apply(text, "#"), text);
+    }
+
+    #[test]
+    fn empty_comment_prefix_strips_no_line() {
+        assert_eq!(CleanupMode::Strip.apply("# a\nb\n", ""), "# a\nb\n");
+        let text = format!("a\n{}\nb\n", scissors_line(""));
+        assert_eq!(CleanupMode::Scissors.apply(&text, ""), "a\n");
+    }
+
+    #[test]
+    fn prepare_appends_commented_help_text() {
+        let cleanup = Cleanup {
+            help_text: Some("Write a message.\n\nEmpty aborts.".to_string()),
+            ..Cleanup::default()
+        };
+        assert_eq!(
+            cleanup.prepare("draft"),
+            "draft\n\n# Write a message.\n#\n# Empty aborts.\n"
+        );
+        assert_eq!(
+            cleanup.prepare(""),
+            "\n# Write a message.\n#\n# Empty aborts.\n"
+        );
+    }
+
+    #[test]
+    fn prepare_adds_the_scissors_line() {
+        let cleanup = Cleanup {
+            mode: CleanupMode::Scissors,
+            comment_prefix: "//".to_string(),
+            help_text: Some("Help".to_string()),
+        };
+        let prepared = cleanup.prepare("text\n");
+        assert_eq!(
+            prepared,
+            "text\n\n\
+             // ------------------------ >8 ------------------------\n\
+             // Do not modify or remove the line above.\n\
+             // Everything below it will be ignored.\n\
+             // Help\n"
+        );
+        assert_eq!(cleanup.apply(&prepared), "text\n");
+    }
+
+    #[test]
+    fn prepare_without_help_text_keeps_the_content() {
+        assert_eq!(Cleanup::default().prepare("text"), "text");
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+use crate::{ArgTemplate, EditorInterface, errors::OpenEditorError};
+
+/// User preferences for the editor, usually read from `$XDG_CONFIG_HOME/open-editor/config.toml`.
+///
+/// ```toml
+/// # Used when no custom environment variable is set, before `VISUAL` and `EDITOR`
+/// editor = "nvim"
+/// # Extra arguments given to `editor`
+/// args = ["--clean"]
+/// # Skip editors opening a window when another one is available ("terminal" or "graphical")
+/// prefer = "terminal"
+///
+/// # Argument syntax of editors open-editor does not know, by program name
+/// [kinds.myedit]
+/// args = ["{wait}", "--goto", "{file}:{line}:{column}"]
+/// wait_args = ["--wait"]
+/// interface = "graphical"
+///
+/// # Editors used for some file extensions
+/// [extensions]
+/// md = "typora"
+///
+/// # Editors used for the files matching glob patterns
+/// [files]
+/// "*.log" = "less"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Command of the preferred editor, which may contain arguments.
+    pub editor: Option<String>,
+    /// Extra arguments given to the preferred editor.
+    pub args: Vec<String>,
+    /// Kind of interface to prefer when several editors are configured.
+    pub prefer: Option<EditorInterface>,
+    /// Argument syntax of custom editors, by program name.
+    pub kinds: HashMap<String, KindConfig>,
+    /// Editor commands to use for some file extensions, without the leading dot.
+    pub extensions: HashMap<String, String>,
+    /// Editor commands to use for the files matching some glob patterns.
+    pub files: HashMap<String, String>,
+}
+
+/// Argument syntax of a custom editor, see [`ArgTemplate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct KindConfig {
+    /// Arguments containing the `{file}`, `{line}`, `{column}` and `{wait}` placeholders.
+    pub args: Vec<String>,
+    /// Arguments replacing `{wait}` when waiting for the editor.
+    pub wait_args: Vec<String>,
+    /// Arguments replacing `{wait}` when not waiting for the editor.
+    pub no_wait_args: Vec<String>,
+    /// Where the editor displays its interface.
+    pub interface: EditorInterface,
+}
+impl From<&KindConfig> for ArgTemplate {
+    fn from(kind: &KindConfig) -> Self {
+        fn strs(args: &[String]) -> Vec<&str> {
+            args.iter().map(String::as_str).collect()
+        }
+        ArgTemplate::new(&strs(&kind.args))
+            .with_wait_args(&strs(&kind.wait_args))
+            .with_no_wait_args(&strs(&kind.no_wait_args))
+            .with_interface(kind.interface)
+    }
+}
+
+impl Config {
+    /// Gets the path of the user's configuration file, `open-editor/config.toml` inside
+    /// `$XDG_CONFIG_HOME` or `~/.config`.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .or_else(|| env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+        Some(config_dir.join("open-editor").join("config.toml"))
+    }
+    /// Loads the user's configuration file, or an empty configuration if there is none.
+    ///
+    /// # Errors
+    /// If the file exists but cannot be read or is not valid.
+    pub fn load() -> Result<Self, OpenEditorError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::from_file(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+    /// Loads a configuration file.
+    ///
+    /// # Errors
+    /// If the file cannot be read or is not valid.
+    pub fn from_file(file_path: &Path) -> Result<Self, OpenEditorError> {
+        let content = std::fs::read_to_string(file_path)
+            .map_err(OpenEditorError::file_manipulation(file_path))?;
+        content
+            .parse()
+            .map_err(|error: toml::de::Error| OpenEditorError::InvalidConfig {
+                file_path: file_path.to_path_buf(),
+                error: Box::new(error),
+            })
+    }
+    /// Gets the command of the preferred editor including the extra arguments.
+    pub(crate) fn editor_command(&self) -> Option<String> {
+        let editor = self.editor.as_ref().filter(|editor| !editor.is_empty())?;
+        if self.args.is_empty() {
+            Some(editor.clone())
+        } else {
+            Some(format!("{editor} {}", shell_words::join(&self.args)))
+        }
+    }
+}
+impl FromStr for Config {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
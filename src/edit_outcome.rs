@@ -0,0 +1,80 @@
+use crate::cleanup::Cleanup;
+
+/// What the user did with a string edited in the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// The string was saved as it was given, or not saved at all.
+    Unchanged,
+    /// Nothing but whitespace is left, after cleanup.
+    Emptied,
+    /// The string was changed to this one.
+    Modified(String),
+}
+
+impl EditOutcome {
+    /// Compares the edited `content` to the `original` string, both being cleaned up the same way.
+    /// An empty content is reported as `Emptied` even if the original string was empty.
+    pub(crate) fn new(original: &str, content: &str, cleanup: &Cleanup) -> Self {
+        if content.trim().is_empty() {
+            EditOutcome::Emptied
+        } else if content == cleanup.apply(&cleanup.prepare(original)) {
+            EditOutcome::Unchanged
+        } else {
+            EditOutcome::Modified(content.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CleanupMode;
+
+    fn strip_with_help() -> Cleanup {
+        Cleanup {
+            mode: CleanupMode::Strip,
+            help_text: Some("Write something.".to_string()),
+            ..Cleanup::default()
+        }
+    }
+
+    #[test]
+    fn saved_as_given_is_unchanged() {
+        let cleanup = strip_with_help();
+        let saved = cleanup.apply(&cleanup.prepare("hello\n"));
+        assert_eq!(
+            EditOutcome::new("hello\n", &saved, &cleanup),
+            EditOutcome::Unchanged
+        );
+        let verbatim = Cleanup::default();
+        assert_eq!(
+            EditOutcome::new("hello", "hello", &verbatim),
+            EditOutcome::Unchanged
+        );
+    }
+
+    #[test]
+    fn only_help_text_left_is_emptied() {
+        let cleanup = strip_with_help();
+        let saved = cleanup.apply("\n# Write something.\n");
+        assert_eq!(
+            EditOutcome::new("hello\n", &saved, &cleanup),
+            EditOutcome::Emptied
+        );
+        // An empty string left empty is reported as emptied, not unchanged
+        assert_eq!(
+            EditOutcome::new("", "  \n", &Cleanup::default()),
+            EditOutcome::Emptied
+        );
+    }
+
+    #[test]
+    fn other_content_is_modified() {
+        let cleanup = strip_with_help();
+        let saved = cleanup.apply("hello world\n\n# Write something.\n");
+        assert_eq!(
+            EditOutcome::new("hello\n", &saved, &cleanup),
+            EditOutcome::Modified("hello world\n".to_string())
+        );
+    }
+}
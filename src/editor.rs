@@ -1,12 +1,17 @@
-use std::{ffi::OsString, path::PathBuf};
+use std::{ffi::OsString, path::PathBuf, sync::Arc};
 
-use crate::{editor_kind::EditorKind, errors::OpenEditorError};
+use crate::{
+    editor_kind::EditorKind,
+    editor_profile::EditorProfile,
+    errors::{EditorCallContext, OpenEditorError},
+};
 
 #[derive(Debug, Clone)]
-/// Represents an editor instance with its type and binary path.
+/// Represents an editor instance with its profile and binary path.
 pub struct Editor {
-    pub(crate) editor_type: EditorKind,
+    pub(crate) profile: Arc<dyn EditorProfile>,
     pub(crate) binary_path: PathBuf,
+    pub(crate) args: Vec<String>,
 }
 
 impl Editor {
@@ -14,25 +19,87 @@ impl Editor {
     #[must_use]
     pub fn from_bin_path(binary_path: PathBuf) -> Self {
         Self {
-            editor_type: EditorKind::UnknownEditor,
+            profile: Arc::new(EditorKind::UnknownEditor),
             binary_path,
+            args: vec![],
         }
     }
     /// Creates a new `Editor` instance from an editor kind. The binary path is determined using the `which` crate.
+    ///
+    /// [`EditorKind::Custom`] and [`EditorKind::UnknownEditor`] have no program name, so the
+    /// binary path is left empty and calling the editor fails with
+    /// [`OpenEditorError::EditorNotFound`]; use [`Editor::new`] with their binary path instead.
     #[must_use]
     pub fn from_editor_kind(editor_type: EditorKind) -> Self {
-        let binary_path = Self::get_full_path(editor_type.to_string().into());
+        let binary_path = editor_type
+            .program_name()
+            .map(|name| Self::get_full_path(name.into()))
+            .unwrap_or_default();
         Self {
-            editor_type,
+            profile: Arc::new(editor_type),
             binary_path,
+            args: vec![],
         }
     }
-    /// Creates a new `Editor` instance with the specified editor type and binary path.
+    /// Creates a new `Editor` instance with the specified profile, such as an [`EditorKind`],
+    /// and binary path.
     #[must_use]
-    pub fn new(editor_type: EditorKind, binary_path: PathBuf) -> Self {
+    pub fn new(profile: impl EditorProfile + 'static, binary_path: PathBuf) -> Self {
         Self {
-            editor_type,
+            profile: Arc::new(profile),
             binary_path,
+            args: vec![],
+        }
+    }
+    /// Creates a new `Editor` instance from a command line such as `code -w` or `emacsclient -t -a ''`.
+    ///
+    /// If the whole command is an executable, such as an unquoted Windows path like
+    /// `C:\Program Files\Notepad++\notepad++.exe`, it is used as is. Otherwise the command
+    /// is split using POSIX shell quoting rules. The first word is the program,
+    /// whose name is used to determine the editor type (see [`EditorKind::from_binary_path`]),
+    /// and the remaining words are
+    /// passed to the editor before the arguments generated for the file to open.
+    ///
+    /// # Errors
+    /// Returns an error if the command cannot be split (e.g. unterminated quotes) or is empty.
+    pub fn from_command(command: &str) -> Result<Self, OpenEditorError> {
+        if let Ok(binary_path) = which::which(command.trim()) {
+            return Ok(Self {
+                profile: Arc::new(EditorKind::from_binary_path(&binary_path)),
+                binary_path,
+                args: vec![],
+            });
+        }
+        let mut words = shell_words::split(command)
+            .map_err(|error| OpenEditorError::InvalidEditorCommand {
+                command: command.to_string(),
+                error: Some(error),
+            })?
+            .into_iter();
+        let Some(program) = words.next() else {
+            return Err(OpenEditorError::InvalidEditorCommand {
+                command: command.to_string(),
+                error: None,
+            });
+        };
+        let binary_path = Self::get_full_path(program.into());
+        Ok(Self {
+            profile: Arc::new(EditorKind::from_binary_path(&binary_path)),
+            binary_path,
+            args: words.collect(),
+        })
+    }
+    /// Gets the profile describing how to invoke the editor.
+    #[must_use]
+    pub fn profile(&self) -> &dyn EditorProfile {
+        self.profile.as_ref()
+    }
+    /// Sets additional arguments passed to the editor before the file related arguments.
+    #[must_use]
+    pub fn with_args(self, args: &[&str]) -> Self {
+        Self {
+            args: args.iter().map(|&s| s.to_string()).collect(),
+            ..self
         }
     }
     /// Gets the full path of the editor binary based on the provided editor name.
@@ -43,31 +110,42 @@ impl Editor {
         }
     }
     /// Validates that the binary path exists and is executable.
-    /// Returns `Ok(())` if the binary is valid, or an `OpenEditorError` if it is not.
-    pub(crate) fn validate_executable(&self) -> Result<(), OpenEditorError> {
+    pub(crate) fn validate_executable(&self) -> Result<(), ExecutableError> {
         if !self.binary_path.exists() || !self.binary_path.is_file() {
-            return Err(OpenEditorError::EditorNotFound {
-                binary_path: self.binary_path.clone(),
-            });
+            return Err(ExecutableError::NotFound);
         }
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            let metadata = std::fs::metadata(&self.binary_path).map_err(|e| {
-                OpenEditorError::EditorNotExecutable {
-                    binary_path: self.binary_path.clone(),
-                    error: Some(e),
-                }
-            })?;
+            let metadata = std::fs::metadata(&self.binary_path)
+                .map_err(|e| ExecutableError::NotExecutable(Some(e)))?;
             let permissions = metadata.permissions();
             if permissions.mode() & 0o111 == 0 {
-                return Err(OpenEditorError::EditorNotExecutable {
-                    binary_path: self.binary_path.clone(),
-                    error: None,
-                });
+                return Err(ExecutableError::NotExecutable(None));
             }
         }
 
         Ok(())
     }
 }
+
+/// Why the binary of an editor cannot be run.
+pub(crate) enum ExecutableError {
+    NotFound,
+    NotExecutable(Option<std::io::Error>),
+}
+impl ExecutableError {
+    /// Converts this into the error reported for the editor `call`.
+    pub(crate) fn into_error(self, editor: &Editor, call: &EditorCallContext) -> OpenEditorError {
+        let binary_path = editor.binary_path.clone();
+        let call = call.clone();
+        match self {
+            ExecutableError::NotFound => OpenEditorError::EditorNotFound { binary_path, call },
+            ExecutableError::NotExecutable(error) => OpenEditorError::EditorNotExecutable {
+                binary_path,
+                call,
+                error,
+            },
+        }
+    }
+}
@@ -1,16 +1,117 @@
 use std::{
-    env::{self, temp_dir},
+    env,
+    fmt::Display,
+    io::IsTerminal,
     path::{Path, PathBuf},
-    process::{Command, Stdio},
+    process::{Child, Command, Stdio},
+    sync::Arc,
 };
 
-use crate::{ENV_VARS, editor::Editor, editor_kind::EditorKind, errors::OpenEditorError};
+#[cfg(feature = "config")]
+use crate::config::Config;
+#[cfg(feature = "serde")]
+use crate::format::Format;
+#[cfg(feature = "tokio")]
+use crate::stderr_capture::wait_capturing_stderr;
+use crate::{
+    ArgTemplate, ENV_VARS, EditorInterface, FALLBACK_EDITORS, FILE_EDITORS_ENV_VAR,
+    backing_file::BackingFile,
+    cleanup::{Cleanup, CleanupMode},
+    edit_outcome::EditOutcome,
+    editor::Editor,
+    editor_handle::EditorHandle,
+    environment::Environment,
+    errors::{EditorCallContext, OpenEditorError},
+    file_rule::FileRule,
+    file_target::FileTarget,
+    git_config,
+    stderr_capture::StderrCapture,
+    terminal::TerminalEmulator,
+    validation::ErrorHeader,
+};
+
+/// What to do with the stderr of the editor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StderrMode {
+    /// Let the editor write to the terminal. Always used for terminal editors.
+    #[default]
+    Inherit,
+    /// Capture stderr so that it is reported in [`OpenEditorError::EditorCallError`].
+    /// Useful for GUI editors such as `code` or `subl`.
+    Capture,
+    /// Capture stderr while still writing it to the terminal.
+    Tee,
+}
+
+/// How the default editor is found.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EditorResolution {
+    /// Custom environment variables, then the configured editor, `VISUAL`, `EDITOR` and the
+    /// fallback editors.
+    #[default]
+    Default,
+    /// Same order as git: custom environment variables, then `GIT_EDITOR`, `core.editor`
+    /// from the git configuration files, the configured editor, `VISUAL`, `EDITOR` and the
+    /// fallback editors.
+    Git,
+}
+
+/// A place where the command of the default editor can be found.
+enum EditorSource {
+    EnvVar(String),
+    GitConfig,
+    Config(String),
+    Fallback(String),
+}
+impl EditorSource {
+    /// Gets the editor command from this source, if it is set.
+    fn command(&self) -> Option<String> {
+        match self {
+            EditorSource::EnvVar(var) => env::var_os(var)
+                .filter(|var| !var.is_empty())
+                .and_then(|v| v.into_string().ok()),
+            EditorSource::GitConfig => git_config::core_editor().filter(|v| !v.is_empty()),
+            EditorSource::Config(command) | EditorSource::Fallback(command) => {
+                Some(command.clone())
+            }
+        }
+    }
+}
+impl Display for EditorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorSource::EnvVar(var) => write!(f, "${var}"),
+            EditorSource::GitConfig => write!(f, "core.editor"),
+            EditorSource::Config(_) => write!(f, "configured editor"),
+            EditorSource::Fallback(command) => write!(f, "{command}"),
+        }
+    }
+}
 
+#[derive(Clone)]
 pub struct EditorCallBuilder {
     editor: Option<Editor>,
     file_path: Option<PathBuf>,
+    temp_file_prefix: String,
+    temp_file_suffix: String,
+    temp_file_name: Option<String>,
+    keep_file: bool,
+    cleanup: Cleanup,
+    abort_on_empty: bool,
+    abort_on_unchanged: bool,
     custom_env_vars: Vec<String>,
+    fallback_editors: Vec<String>,
+    configured_editor: Option<String>,
+    preferred_interface: Option<EditorInterface>,
+    check_environment: bool,
+    open_in_terminal: bool,
+    terminal: Option<TerminalEmulator>,
+    custom_kinds: Vec<(String, ArgTemplate)>,
+    file_editors: Vec<FileRule>,
+    configured_file_editors: Vec<FileRule>,
+    resolution: EditorResolution,
     wait: bool,
+    stderr_mode: StderrMode,
     line_number: usize,
     column_number: usize,
 }
@@ -19,8 +120,26 @@ impl Default for EditorCallBuilder {
         Self {
             editor: None,
             file_path: None,
+            temp_file_prefix: String::from("open_editor_"),
+            temp_file_suffix: String::new(),
+            temp_file_name: None,
+            keep_file: false,
+            cleanup: Cleanup::default(),
+            abort_on_empty: false,
+            abort_on_unchanged: false,
             custom_env_vars: vec![],
+            fallback_editors: FALLBACK_EDITORS.iter().map(|&s| s.to_string()).collect(),
+            configured_editor: None,
+            preferred_interface: None,
+            check_environment: false,
+            open_in_terminal: false,
+            terminal: None,
+            custom_kinds: vec![],
+            file_editors: vec![],
+            configured_file_editors: vec![],
+            resolution: EditorResolution::Default,
             wait: true,
+            stderr_mode: StderrMode::Inherit,
             line_number: 1,
             column_number: 1,
         }
@@ -49,13 +168,21 @@ impl EditorCallBuilder {
     }
     #[must_use]
     /// Sets the column number for the editor to open at.
-    pub fn at_column(self, line: usize) -> Self {
+    ///
+    /// Columns start at 1 and are counted in characters, so a multibyte character counts as
+    /// one column. Editors that count bytes or screen cells may land elsewhere on lines
+    /// containing such characters or tabs.
+    pub fn at_column(self, column: usize) -> Self {
         Self {
-            column_number: line,
+            column_number: column,
             ..self
         }
     }
     /// Whether to wait for the editor to close before returning.
+    ///
+    /// GUI editors that return immediately are given the flag making them block (e.g.
+    /// `code -w`), and those that block are given the flag making them detach when
+    /// possible. See [`WaitBehavior`](crate::WaitBehavior).
     #[must_use]
     pub fn wait_for_editor(self, value: bool) -> Self {
         Self {
@@ -63,6 +190,17 @@ impl EditorCallBuilder {
             ..self
         }
     }
+    /// Sets what to do with the stderr of the editor. Defaults to [`StderrMode::Inherit`].
+    ///
+    /// Terminal editors such as vim or nano always inherit stderr, since capturing it would
+    /// break them, unless they are run in a terminal emulator.
+    #[must_use]
+    pub fn with_stderr_mode(self, stderr_mode: StderrMode) -> Self {
+        Self {
+            stderr_mode,
+            ..self
+        }
+    }
     /// Add additional environment variables to look for the editor in. These variables
     /// will have higher priority than `VISUAL` and `EDITOR`.
     #[must_use]
@@ -74,6 +212,104 @@ impl EditorCallBuilder {
             ..self
         }
     }
+    /// Add editor commands to try when no environment variable is set. These commands
+    /// will have lower priority than the default ones (`sensible-editor`, `editor`,
+    /// `nano`, `vim` and `vi`), and are only used if they can be found in the `PATH`.
+    #[must_use]
+    pub fn with_fallback_editors(self, commands: &[&str]) -> Self {
+        let mut fallback_editors = self.fallback_editors;
+        fallback_editors.extend(commands.iter().map(|&s| s.to_string()));
+        Self {
+            fallback_editors,
+            ..self
+        }
+    }
+    /// Replace the editor commands to try when no environment variable is set.
+    /// An empty list disables the fallback.
+    #[must_use]
+    pub fn set_fallback_editors(self, commands: &[&str]) -> Self {
+        Self {
+            fallback_editors: commands.iter().map(|&s| s.to_string()).collect(),
+            ..self
+        }
+    }
+    /// Sets how the default editor is found. Use [`EditorResolution::Git`] to use the same
+    /// editor as git.
+    #[must_use]
+    pub fn with_resolution(self, resolution: EditorResolution) -> Self {
+        Self { resolution, ..self }
+    }
+    /// Prefers editors with this interface: editors known to have another interface are
+    /// skipped when looking for the default editor, unless no other editor is found.
+    #[must_use]
+    pub fn prefer_interface(self, interface: EditorInterface) -> Self {
+        Self {
+            preferred_interface: Some(interface),
+            ..self
+        }
+    }
+    /// Whether to skip the editors that cannot run in the current [`Environment`]: terminal
+    /// editors when stdin is not a terminal, and graphical editors when there is no display,
+    /// such as in SSH sessions without X11 forwarding.
+    ///
+    /// When `TERM` is `dumb` or stdin is not a terminal, `EDITOR` is also looked for before
+    /// `VISUAL`, as `VISUAL` is meant for full-screen editors. An editor set with `with_editor`
+    /// is never skipped.
+    #[must_use]
+    pub fn check_environment(self, value: bool) -> Self {
+        Self {
+            check_environment: value,
+            ..self
+        }
+    }
+    /// Whether to run terminal editors in a new terminal emulator window when stdin is not a
+    /// terminal, as in GUI applications and daemons. The terminal emulator is found with
+    /// [`TerminalEmulator::detect`] unless one is set with `with_terminal_emulator`.
+    ///
+    /// Every editor but the graphical ones is run in the terminal emulator, including those
+    /// whose interface is unknown, such as `sensible-editor`, or adaptive, such as `emacsclient`.
+    #[must_use]
+    pub fn open_in_terminal(self, value: bool) -> Self {
+        Self {
+            open_in_terminal: value,
+            ..self
+        }
+    }
+    /// Sets the terminal emulator used to run terminal editors, see `open_in_terminal`.
+    #[must_use]
+    pub fn with_terminal_emulator(self, terminal: TerminalEmulator) -> Self {
+        Self {
+            terminal: Some(terminal),
+            ..self
+        }
+    }
+    /// Uses `template` as the argument syntax of the default editor when its program is named
+    /// `name`, for editors that are not known by [`EditorKind`](crate::EditorKind).
+    #[must_use]
+    pub fn with_custom_kind(self, name: &str, template: ArgTemplate) -> Self {
+        let mut custom_kinds = self.custom_kinds;
+        custom_kinds.push((name.to_string(), template));
+        Self {
+            custom_kinds,
+            ..self
+        }
+    }
+    /// Uses the editor `command` for the files matching the glob `pattern`, such as `*.md`.
+    ///
+    /// Patterns containing a `/` are matched against the whole path and others against the
+    /// file name, `*` matches any characters, `?` a single one, and ASCII case is ignored.
+    /// Rules added later take precedence, and all of them over the rules of the
+    /// `OPEN_EDITOR_FILE_EDITORS` environment variable, written like `*.md=typora;*.log=less`.
+    /// They are ignored if an editor is set with `with_editor`.
+    #[must_use]
+    pub fn with_file_editor(self, pattern: &str, command: &str) -> Self {
+        let mut file_editors = self.file_editors;
+        file_editors.push(FileRule::new(pattern, command));
+        Self {
+            file_editors,
+            ..self
+        }
+    }
     /// Sets a specific editor to use instead of the default one.
     #[must_use]
     pub fn with_editor(self, editor: Editor) -> Self {
@@ -82,6 +318,114 @@ impl EditorCallBuilder {
             ..self
         }
     }
+    /// Sets the prefix of the temporary file created by `edit_string`.
+    #[must_use]
+    pub fn with_temp_file_prefix(self, prefix: &str) -> Self {
+        Self {
+            temp_file_prefix: prefix.to_string(),
+            ..self
+        }
+    }
+    /// Sets the suffix of the temporary file created by `edit_string`, usually an extension
+    /// such as `.md` or `.toml` so that the editor can apply syntax highlighting.
+    #[must_use]
+    pub fn with_temp_file_suffix(self, suffix: &str) -> Self {
+        Self {
+            temp_file_suffix: suffix.to_string(),
+            ..self
+        }
+    }
+    /// Sets the full name of the temporary file created by `edit_string`, like git's `COMMIT_EDITMSG`.
+    ///
+    /// The file is created inside a private temporary directory, and the suffix set with
+    /// `with_temp_file_suffix` is ignored. Editing fails if the name is not a single path
+    /// component, such as an absolute path or one containing `..`.
+    #[must_use]
+    pub fn with_temp_file_name(self, file_name: &str) -> Self {
+        Self {
+            temp_file_name: Some(file_name.to_string()),
+            ..self
+        }
+    }
+    /// Sets the file used by `edit_string` instead of a temporary file.
+    ///
+    /// The content of the file is replaced with the string to edit, and the temporary file
+    /// options are ignored. A file that already exists belongs to the caller and is never
+    /// removed; one created by `edit_string` is removed afterwards unless `keep_file` is set.
+    #[must_use]
+    pub fn with_file_path(self, file_path: &Path) -> Self {
+        Self {
+            file_path: Some(file_path.to_path_buf()),
+            ..self
+        }
+    }
+    /// Whether to keep the file used by `edit_string` on disk after editing,
+    /// for crash recovery or auditing. The file is removed by default.
+    ///
+    /// When kept, the file stays on disk even if the editor fails to start or the
+    /// [`EditorHandle`] of `spawn_string` is dropped without waiting for it.
+    #[must_use]
+    pub fn keep_file(self, value: bool) -> Self {
+        Self {
+            keep_file: value,
+            ..self
+        }
+    }
+    /// Sets a help text appended to the string edited by `edit_string`, after an empty line
+    /// and with each line commented. Use [`CleanupMode::Strip`] or [`CleanupMode::Scissors`]
+    /// to remove it after editing.
+    #[must_use]
+    pub fn with_help_text(self, help_text: &str) -> Self {
+        Self {
+            cleanup: Cleanup {
+                help_text: Some(help_text.to_string()),
+                ..self.cleanup
+            },
+            ..self
+        }
+    }
+    /// Sets the prefix of comment lines, used for the help text and when cleaning up the
+    /// edited string. Defaults to `#`.
+    #[must_use]
+    pub fn with_comment_prefix(self, comment_prefix: &str) -> Self {
+        Self {
+            cleanup: Cleanup {
+                comment_prefix: comment_prefix.to_string(),
+                ..self.cleanup
+            },
+            ..self
+        }
+    }
+    /// Sets how the string edited by `edit_string` is cleaned up after editing.
+    /// Defaults to [`CleanupMode::Verbatim`].
+    #[must_use]
+    pub fn with_cleanup(self, mode: CleanupMode) -> Self {
+        Self {
+            cleanup: Cleanup {
+                mode,
+                ..self.cleanup
+            },
+            ..self
+        }
+    }
+    /// Whether `edit_string` fails with [`OpenEditorError::EditAborted`] when nothing but
+    /// whitespace is left after cleanup, like `git commit` with an empty message.
+    #[must_use]
+    pub fn abort_on_empty(self, value: bool) -> Self {
+        Self {
+            abort_on_empty: value,
+            ..self
+        }
+    }
+    /// Whether `edit_string` fails with [`OpenEditorError::EditAborted`] when the string is
+    /// left unchanged. An emptied string is only considered by `abort_on_empty`.
+    #[must_use]
+    pub fn abort_on_unchanged(self, value: bool) -> Self {
+        Self {
+            abort_on_unchanged: value,
+            ..self
+        }
+    }
     /// Open the default editor and returns what was written in it.
     ///
     /// # Errors
@@ -105,114 +449,818 @@ impl EditorCallBuilder {
     ///
     /// # Errors
     /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
-    /// or if the editor call fails.
+    /// or if the edit is aborted (see `abort_on_empty` and `abort_on_unchanged`).
     pub fn edit_string(&self, string: &str) -> Result<String, OpenEditorError> {
-        let file_path = match &self.file_path {
-            Some(path) => path,
-            None => &{
-                let mut filename = temp_dir();
-                filename.push(String::from("open_editor_tmp_file"));
-                filename
-            },
-        };
-        // Write the initial content to the temporary file
-        std::fs::write(file_path, string).map_err(OpenEditorError::FileManipulationFail)?;
-        self.open_file(file_path)?;
-        let result =
-            std::fs::read_to_string(file_path).map_err(OpenEditorError::FileManipulationFail)?;
-
-        // Clean up the temporary file after reading
-        std::fs::remove_file(file_path).map_err(|_| {
-            OpenEditorError::TempFileCleanupFail(file_path.to_string_lossy().into_owned())
-        })?;
-
-        Ok(result)
+        let content = self.edit_contents(string)?;
+        self.edit_outcome(string, &content)?;
+        Ok(content)
+    }
+    /// Open the default editor to edit a string and tells whether the user changed it.
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
+    /// or if the edit is aborted (see `abort_on_empty` and `abort_on_unchanged`).
+    pub fn edit_string_with_outcome(&self, string: &str) -> Result<EditOutcome, OpenEditorError> {
+        let content = self.edit_contents(string)?;
+        self.edit_outcome(string, &content)
+    }
+    /// Edits a string until `validate` accepts it, and returns what `validate` returned.
+    ///
+    /// When `validate` fails, the editor is reopened with the edited string, preceded by the
+    /// error as comment lines which are removed after editing. The cursor is placed at the
+    /// position of the error if it is a [`ValidationError`](crate::ValidationError).
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
+    /// or if the edit is aborted: the user emptied the string or left it unchanged after an
+    /// error.
+    pub fn edit_until_valid<T, E: Display + 'static>(
+        &self,
+        string: &str,
+        mut validate: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<T, OpenEditorError> {
+        let mut validation = Validation::new(string);
+        loop {
+            let (builder, original) = validation.next_edit(self);
+            let content = builder.edit_contents(&original)?;
+            if let Some(value) = validation.check(self, &original, &content, &mut validate)? {
+                return Ok(value);
+            }
+        }
+    }
+    /// Edits a string in the editor and returns the cleaned up content of the file.
+    fn edit_contents(&self, string: &str) -> Result<String, OpenEditorError> {
+        let file = self.create_backing_file()?;
+        // Write the initial content to the file
+        let content = file.write(&self.cleanup.prepare(string)).and_then(|()| {
+            self.open_file(file.path())?;
+            std::fs::read_to_string(file.path())
+                .map_err(OpenEditorError::file_manipulation(file.path()))
+        });
+        self.finish_edit(file, content)
     }
     /// Opens the specified file in the editor.
     ///
     /// # Errors
     /// This function will return an error if the editor call fails or if the file cannot be read.
     pub fn open_file(&self, file_path: &Path) -> Result<(), OpenEditorError> {
-        let editor = match &self.editor {
-            Some(editor) => editor,
-            None => &self.get_default_editor()?,
+        self.editor_call(file_path, self.wait)?.call()
+    }
+    /// Opens several files in the editor, each at its own position.
+    ///
+    /// The files are opened in a single session when they are all opened with the same editor
+    /// and it can take several files (e.g. in tabs with vim), and one after the other otherwise,
+    /// or when the editor cannot give each file its own position.
+    /// The line and column set on the builder are not used.
+    ///
+    /// # Errors
+    /// This function will return an error if an editor call fails.
+    pub fn open_files(&self, files: &[FileTarget]) -> Result<(), OpenEditorError> {
+        if let [file] = files {
+            return self.target_call(file, self.wait)?.call();
+        }
+        match self.files_call(files, self.wait)? {
+            Some(call) => call.call(),
+            None => files
+                .iter()
+                .try_for_each(|file| self.target_call(file, self.wait)?.call()),
+        }
+    }
+    /// Open the default editor in the background and return a handle to it.
+    /// The content can be retrieved with [`EditorHandle::wait_for_contents`].
+    ///
+    /// # Errors
+    /// If the editor cannot be spawned, or if the temporary file cannot be created.
+    pub fn spawn_editor(&self) -> Result<EditorHandle, OpenEditorError> {
+        self.spawn_string("")
+    }
+    /// Open the default editor in the background to edit a string and return a handle to it.
+    /// The edited string can be retrieved with [`EditorHandle::wait_for_contents`].
+    ///
+    /// # Errors
+    /// If the editor cannot be spawned, or if the temporary file cannot be created.
+    pub fn spawn_string(&self, string: &str) -> Result<EditorHandle, OpenEditorError> {
+        let file = self.create_backing_file()?;
+        let child = file
+            .write(&self.cleanup.prepare(string))
+            .and_then(|()| self.editor_call(file.path(), true)?.spawn());
+        match child {
+            Ok((call, child, stderr)) => Ok(EditorHandle::new(
+                call,
+                child,
+                stderr,
+                file.path().to_path_buf(),
+                Some(file),
+                self.keep_file,
+                self.cleanup.clone(),
+            )),
+            Err(e) => {
+                let _ = self.release_backing_file(file);
+                Err(e)
+            }
+        }
+    }
+    /// Opens the specified file in the editor in the background and return a handle to it.
+    ///
+    /// The editor is asked to block until the file is closed (e.g. `code -w`) regardless of
+    /// `wait_for_editor`, so that the handle tracks the editing session.
+    ///
+    /// # Errors
+    /// If the editor cannot be spawned.
+    pub fn spawn_file(&self, file_path: &Path) -> Result<EditorHandle, OpenEditorError> {
+        let (call, child, stderr) = self.editor_call(file_path, true)?.spawn()?;
+        Ok(EditorHandle::new(
+            call,
+            child,
+            stderr,
+            file_path.to_path_buf(),
+            None,
+            self.keep_file,
+            Cleanup::default(),
+        ))
+    }
+    /// Compares the edited content to the original string, failing if the edit must be aborted.
+    fn edit_outcome(&self, original: &str, content: &str) -> Result<EditOutcome, OpenEditorError> {
+        match EditOutcome::new(original, content, &self.cleanup) {
+            outcome @ EditOutcome::Emptied if self.abort_on_empty => {
+                Err(OpenEditorError::EditAborted { outcome })
+            }
+            outcome @ EditOutcome::Unchanged if self.abort_on_unchanged => {
+                Err(OpenEditorError::EditAborted { outcome })
+            }
+            outcome => Ok(outcome),
+        }
+    }
+    /// Creates the file used to edit strings.
+    fn create_backing_file(&self) -> Result<BackingFile, OpenEditorError> {
+        match (&self.file_path, &self.temp_file_name) {
+            (Some(path), _) => Ok(BackingFile::given(path)),
+            (None, Some(file_name)) => BackingFile::named(&self.temp_file_prefix, file_name),
+            (None, None) => BackingFile::random(&self.temp_file_prefix, &self.temp_file_suffix),
+        }
+    }
+    /// Removes the file used to edit strings, unless it must be kept.
+    fn release_backing_file(&self, file: BackingFile) -> Result<(), OpenEditorError> {
+        if self.keep_file {
+            file.keep()
+        } else {
+            file.close()
+        }
+    }
+    /// Releases the file used to edit a string and cleans up its `content`, read after editing.
+    fn finish_edit(
+        &self,
+        file: BackingFile,
+        content: Result<String, OpenEditorError>,
+    ) -> Result<String, OpenEditorError> {
+        // Clean up the file after reading, even if the editor call failed
+        let released = self.release_backing_file(file);
+        let content = content?;
+        released?;
+        Ok(self.cleanup.apply(&content))
+    }
+    /// Builds the call to the editor for the specified file.
+    fn editor_call(&self, file_path: &Path, wait: bool) -> Result<EditorCall, OpenEditorError> {
+        let target = FileTarget::new(file_path)
+            .at_line(self.line_number)
+            .at_column(self.column_number);
+        self.target_call(&target, wait)
+    }
+    /// Builds the call opening a single file at its position, or at the start if it has none.
+    fn target_call(&self, target: &FileTarget, wait: bool) -> Result<EditorCall, OpenEditorError> {
+        let (editor, terminal) = self.resolve_editor(&target.path)?;
+        let (line, column) = target.position().unwrap_or((1, 1));
+        let args = editor.profile.editor_args(&target.path, wait, line, column);
+        Ok(self.new_call(editor, terminal, &target.path, args, wait))
+    }
+    /// Builds the call opening all the files in a single session, or `None` if they are not all
+    /// opened with the same editor or if it cannot take several files.
+    fn files_call(
+        &self,
+        targets: &[FileTarget],
+        wait: bool,
+    ) -> Result<Option<EditorCall>, OpenEditorError> {
+        let mut editors = targets
+            .iter()
+            .map(|target| self.resolve_editor(&target.path))
+            .collect::<Result<Vec<_>, _>>()?;
+        if editors.is_empty() {
+            return Ok(None);
+        }
+        let (editor, terminal) = editors.swap_remove(0);
+        if editors
+            .iter()
+            .any(|(other, _)| other.binary_path != editor.binary_path || other.args != editor.args)
+        {
+            return Ok(None);
+        }
+        let Some(args) = editor.profile.multi_file_args(targets, wait) else {
+            return Ok(None);
         };
-
-        // Build the actual Editor Call
-        let editor_call = EditorCall {
-            editor: editor.clone(),
+        Ok(Some(self.new_call(
+            editor,
+            terminal,
+            &targets[0].path,
+            args,
+            wait,
+        )))
+    }
+    fn new_call(
+        &self,
+        editor: Editor,
+        terminal: Option<TerminalEmulator>,
+        file_path: &Path,
+        args: Vec<String>,
+        wait: bool,
+    ) -> EditorCall {
+        let interface = editor.profile.interface();
+        let is_terminal = interface == EditorInterface::Terminal;
+        let terminal = terminal.filter(|_| needs_terminal_emulator(interface));
+        // Terminal editors draw on stderr, so it is only captured when they get their own terminal
+        let stderr_mode = if is_terminal && terminal.is_none() {
+            StderrMode::Inherit
+        } else {
+            self.stderr_mode
+        };
+        EditorCall {
+            terminal,
+            editor,
             file_path: file_path.to_path_buf(),
-            wait: self.wait,
-            line_number: self.line_number,
-            column_number: self.column_number,
+            args,
+            wait,
+            stderr_mode,
+        }
+    }
+    /// Finds the editor to open `file_path` with, and the terminal emulator to run it in if it
+    /// cannot run in the terminal of this process.
+    fn resolve_editor(
+        &self,
+        file_path: &Path,
+    ) -> Result<(Editor, Option<TerminalEmulator>), OpenEditorError> {
+        let environment = self.check_environment.then(Environment::detect);
+        let terminal = self.terminal_emulator();
+        let file_editor = self
+            .file_editor(file_path)
+            .map(|command| self.editor_from_command(&command))
+            .transpose()?
+            .filter(|editor| {
+                environment.is_none_or(|env| {
+                    unusable_reason(env, editor.profile.interface(), terminal.as_ref()).is_none()
+                })
+            });
+        let editor = match (&self.editor, file_editor) {
+            (Some(editor), _) => editor.clone(),
+            (None, Some(editor)) => editor,
+            (None, None) => self.get_default_editor(environment, terminal.as_ref())?,
         };
-        editor_call.call()
-    }
-    /// Gets the default editor from the environment variables `VISUAL` or `EDITOR`.
-    fn get_default_editor(&self) -> Result<Editor, OpenEditorError> {
-        self.custom_env_vars
-            .clone()
-            .into_iter()
-            .chain(ENV_VARS.iter().map(|&s| s.to_string()))
-            .filter_map(env::var_os)
-            .filter(|var| !var.is_empty())
-            .map(|v| {
-                let path = Editor::get_full_path(v.clone());
-                (v.into_string().ok(), path)
-            })
-            .filter_map(|(v, path)| v.map(|v| (v, path)))
-            .map(|(v, cmd)| (Editor::new(EditorKind::from(v), cmd)))
-            .next()
-            .ok_or(OpenEditorError::NoEditorFound)
+        Ok((editor, terminal))
+    }
+    /// Gets the terminal emulator to run terminal editors in, if they cannot run in the
+    /// terminal of this process.
+    fn terminal_emulator(&self) -> Option<TerminalEmulator> {
+        if !self.open_in_terminal || std::io::stdin().is_terminal() {
+            return None;
+        }
+        self.terminal.clone().or_else(TerminalEmulator::detect)
+    }
+    /// Gets the editor command set for the specified file by a rule, if any.
+    fn file_editor(&self, file_path: &Path) -> Option<String> {
+        let env_rules = env::var(FILE_EDITORS_ENV_VAR)
+            .map(|rules| FileRule::parse_list(&rules))
+            .unwrap_or_default();
+        [
+            &self.file_editors,
+            &env_rules,
+            &self.configured_file_editors,
+        ]
+        .into_iter()
+        .find_map(|rules| rules.iter().rfind(|rule| rule.matches(file_path)))
+        .map(|rule| rule.command().to_string())
+    }
+    /// Parses an editor command, using the custom kind registered for its program if any.
+    fn editor_from_command(&self, command: &str) -> Result<Editor, OpenEditorError> {
+        let editor = Editor::from_command(command)?;
+        let name = editor
+            .binary_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.strip_suffix(".exe").unwrap_or(name));
+        match self
+            .custom_kinds
+            .iter()
+            .rfind(|(kind, _)| Some(kind.as_str()) == name)
+        {
+            Some((_, template)) => Ok(Editor {
+                profile: Arc::new(template.clone()),
+                ..editor
+            }),
+            None => Ok(editor),
+        }
+    }
+    /// Lists the places where the default editor command is looked for, by priority.
+    fn editor_sources(&self, environment: Option<Environment>) -> Vec<EditorSource> {
+        let custom_vars = self
+            .custom_env_vars
+            .iter()
+            .cloned()
+            .map(EditorSource::EnvVar);
+        let git = match self.resolution {
+            EditorResolution::Default => vec![],
+            EditorResolution::Git => vec![
+                EditorSource::EnvVar(String::from("GIT_EDITOR")),
+                EditorSource::GitConfig,
+            ],
+        };
+        let mut default_vars = ENV_VARS
+            .iter()
+            .map(|&s| EditorSource::EnvVar(s.to_string()))
+            .collect::<Vec<_>>();
+        if environment.is_some_and(|env| env.dumb_terminal || !env.stdin_is_terminal) {
+            // `VISUAL` is the full-screen editor, `EDITOR` the one for terminals that cannot
+            // run it, like git does when `TERM` is `dumb`
+            default_vars.reverse();
+        }
+        custom_vars
+            .chain(git)
+            .chain(self.configured_editor.clone().map(EditorSource::Config))
+            .chain(default_vars)
+            .chain(
+                self.fallback_editors
+                    .iter()
+                    .cloned()
+                    .map(EditorSource::Fallback),
+            )
+            .collect()
+    }
+    /// Gets the default editor from the environment variables `VISUAL` or `EDITOR`,
+    /// or from the fallback editors if none is set.
+    ///
+    /// The value of the variable is parsed as a shell command, so it may contain arguments.
+    /// Fallback editors are only used if they can be found in the `PATH`, and editors that
+    /// cannot run in `environment` are skipped.
+    fn get_default_editor(
+        &self,
+        environment: Option<Environment>,
+        terminal: Option<&TerminalEmulator>,
+    ) -> Result<Editor, OpenEditorError> {
+        let mut tried = vec![];
+        let mut skipped = vec![];
+        let mut first_found = None;
+        for source in self.editor_sources(environment) {
+            tried.push(source.to_string());
+            let Some(command) = source.command() else {
+                continue;
+            };
+            let editor = match (&source, self.editor_from_command(&command)) {
+                (EditorSource::Fallback(_), Ok(editor))
+                    if editor.validate_executable().is_err() =>
+                {
+                    continue;
+                }
+                (EditorSource::Fallback(_), Err(_)) => continue,
+                (_, editor) => editor?,
+            };
+            let interface = editor.profile.interface();
+            if let Some(reason) =
+                environment.and_then(|env| unusable_reason(env, interface, terminal))
+            {
+                skipped.push(format!("`{command}` {reason}"));
+                continue;
+            }
+            match self.preferred_interface {
+                Some(preferred)
+                    if matches!(
+                        interface,
+                        EditorInterface::Terminal | EditorInterface::Graphical
+                    ) && interface != preferred =>
+                {
+                    first_found.get_or_insert(editor);
+                }
+                _ => return Ok(editor),
+            }
+        }
+        first_found.ok_or(if skipped.is_empty() {
+            OpenEditorError::NoEditorFound { tried }
+        } else {
+            OpenEditorError::NoUsableEditor { skipped }
+        })
+    }
+}
+
+/// The state of [`EditorCallBuilder::edit_until_valid`] between two edits.
+struct Validation {
+    /// The last text submitted to the validation function.
+    text: String,
+    /// The header describing the last validation error, if any.
+    header: Option<ErrorHeader>,
+}
+impl Validation {
+    fn new(string: &str) -> Self {
+        Self {
+            text: string.to_string(),
+            header: None,
+        }
+    }
+    /// Gets the builder and the content for the next edit, with the cursor at the position of
+    /// the last error.
+    fn next_edit(&self, builder: &EditorCallBuilder) -> (EditorCallBuilder, String) {
+        match &self.header {
+            Some(header) => (
+                builder
+                    .clone()
+                    .at_line(header.line)
+                    .at_column(header.column),
+                format!("{}{}", header.text, self.text),
+            ),
+            None => (builder.clone(), self.text.clone()),
+        }
+    }
+    /// Validates the edited `content` of `original`, returning `None` if it must be edited again.
+    ///
+    /// After an error, the edit is aborted if the rejected text is submitted again.
+    fn check<T, E: Display + 'static>(
+        &mut self,
+        builder: &EditorCallBuilder,
+        original: &str,
+        content: &str,
+        validate: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<Option<T>, OpenEditorError> {
+        let text = self
+            .header
+            .as_ref()
+            .map_or(content, |header| header.strip(content));
+        if text.trim().is_empty() {
+            return Err(OpenEditorError::EditAborted {
+                outcome: EditOutcome::Emptied,
+            });
+        }
+        match self.header {
+            None => {
+                builder.edit_outcome(original, content)?;
+            }
+            Some(_) if text == self.text => {
+                return Err(OpenEditorError::EditAborted {
+                    outcome: EditOutcome::Unchanged,
+                });
+            }
+            Some(_) => {}
+        }
+        self.text = text.to_string();
+        match validate(&self.text) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => {
+                self.header = Some(ErrorHeader::new(&error, &builder.cleanup));
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Whether an editor with this interface is run in the terminal emulator, when there is one.
+/// Only graphical editors are not, as editors with an unknown interface, such as
+/// `sensible-editor`, and adaptive ones, such as `emacsclient -t`, may need a terminal.
+fn needs_terminal_emulator(interface: EditorInterface) -> bool {
+    interface != EditorInterface::Graphical
+}
+/// Explains why an editor with this interface cannot run in `environment`, if it cannot.
+/// Editors opened in a terminal emulator need a display, like graphical ones.
+fn unusable_reason(
+    environment: Environment,
+    interface: EditorInterface,
+    terminal: Option<&TerminalEmulator>,
+) -> Option<&'static str> {
+    match terminal {
+        Some(_) if needs_terminal_emulator(interface) => {
+            environment.unusable_reason(EditorInterface::Graphical)
+        }
+        _ => environment.unusable_reason(interface),
     }
 }
 /// Represents a call to an editor with specific options.
 struct EditorCall {
     editor: Editor,
+    terminal: Option<TerminalEmulator>,
     file_path: PathBuf,
+    args: Vec<String>,
     wait: bool,
-    line_number: usize,
-    column_number: usize,
+    stderr_mode: StderrMode,
 }
 impl EditorCall {
+    /// Spawns the editor with options from the [`EditorCallBuilder`] without waiting for it.
+    /// # Errors
+    ///
+    /// This function will return an error if the editor is not a valid executable or if the command fails to spawn.
+    pub fn spawn(
+        &self,
+    ) -> Result<(EditorCallContext, Child, Option<StderrCapture>), OpenEditorError> {
+        let call = self.context();
+        let mut child = self
+            .command(&call)?
+            .spawn()
+            .map_err(|e| OpenEditorError::CommandFail {
+                call: call.clone(),
+                error: e,
+            })?;
+        let stderr = child
+            .stderr
+            .take()
+            .map(|stderr| StderrCapture::start(stderr, self.stderr_mode == StderrMode::Tee));
+        Ok((call, child, stderr))
+    }
+    /// Describes the call for diagnostics, including all the arguments passed to the editor.
+    fn context(&self) -> EditorCallContext {
+        let args = [self.editor.args.clone(), self.args.clone()].concat();
+        match &self.terminal {
+            Some(terminal) => {
+                let command = [
+                    vec![self.editor.binary_path.to_string_lossy().into_owned()],
+                    args,
+                ]
+                .concat();
+                EditorCallContext {
+                    binary_path: terminal.binary_path.clone(),
+                    args: terminal.wrap_args(&command),
+                    file_path: self.file_path.clone(),
+                }
+            }
+            None => EditorCallContext {
+                binary_path: self.editor.binary_path.clone(),
+                args,
+                file_path: self.file_path.clone(),
+            },
+        }
+    }
+    /// Builds the command running the editor.
+    /// # Errors
+    ///
+    /// This function will return an error if the editor is not a valid executable.
+    fn command(&self, call: &EditorCallContext) -> Result<Command, OpenEditorError> {
+        // Ensure the editor binary is valid
+        self.editor
+            .validate_executable()
+            .map_err(|e| e.into_error(&self.editor, call))?;
+        let mut command = Command::new(&call.binary_path);
+        command
+            .args(&call.args)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(match self.stderr_mode {
+                StderrMode::Inherit => Stdio::inherit(),
+                StderrMode::Capture | StderrMode::Tee => Stdio::piped(),
+            });
+        Ok(command)
+    }
     /// Calls the editor with options from the [`EditorCallBuilder`].
     /// # Errors
     ///
     /// This function will return an error if the commands fails to execute or if the editor returns a non-zero exit code.
     pub fn call(&self) -> Result<(), OpenEditorError> {
-        self.editor.validate_executable()?; // Ensure the editor binary is valid
-        let command = Command::new(&self.editor.binary_path)
-            .args(self.editor.editor_type.get_editor_args(
-                &self.file_path,
-                self.wait,
-                self.line_number,
-                self.column_number,
-            ))
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .spawn();
-
+        let (call, mut child, stderr) = self.spawn()?;
         if !self.wait {
             return Ok(());
         }
+        let status = child.wait().map_err(|e| OpenEditorError::CommandFail {
+            call: call.clone(),
+            error: e,
+        })?;
+        EditorHandle::check_status(
+            &call,
+            status,
+            stderr.map(StderrCapture::finish).unwrap_or_default(),
+        )
+    }
+}
 
-        match command {
-            Ok(output) => {
-                let output = output
-                    .wait_with_output()
-                    .map_err(|e| OpenEditorError::CommandFail { error: e })?;
-                if output.status.success() {
-                    Ok(())
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    Err(OpenEditorError::EditorCallError {
-                        exit_code: output.status.code(),
-                        stderr: stderr.to_string(),
-                    })
-                }
+#[cfg(feature = "config")]
+impl EditorCallBuilder {
+    /// Applies the user's preferences from a [`Config`].
+    ///
+    /// The configured editor comes after the custom environment variables (and `GIT_EDITOR`
+    /// and `core.editor` with [`EditorResolution::Git`]) but before `VISUAL` and `EDITOR`.
+    /// Editors set per file have a lower priority than the ones set with `with_file_editor`
+    /// and in the `OPEN_EDITOR_FILE_EDITORS` environment variable.
+    #[must_use]
+    pub fn with_config(self, config: &Config) -> Self {
+        let mut custom_kinds = self.custom_kinds;
+        let mut kinds = config.kinds.iter().collect::<Vec<_>>();
+        kinds.sort_by_key(|(name, _)| name.as_str());
+        custom_kinds.extend(
+            kinds
+                .into_iter()
+                .map(|(name, kind)| (name.clone(), kind.into())),
+        );
+        let mut configured_file_editors = self.configured_file_editors;
+        let mut extensions = config.extensions.iter().collect::<Vec<_>>();
+        extensions.sort();
+        configured_file_editors.extend(extensions.into_iter().map(|(extension, command)| {
+            let pattern = format!("*.{}", extension.trim_start_matches('.'));
+            FileRule::new(&pattern, command)
+        }));
+        let mut files = config.files.iter().collect::<Vec<_>>();
+        files.sort();
+        configured_file_editors.extend(
+            files
+                .into_iter()
+                .map(|(pattern, command)| FileRule::new(pattern, command)),
+        );
+        Self {
+            configured_editor: config.editor_command().or(self.configured_editor),
+            preferred_interface: config.prefer.or(self.preferred_interface),
+            custom_kinds,
+            configured_file_editors,
+            ..self
+        }
+    }
+    /// Applies the user's preferences from their configuration file, see [`Config::load`].
+    ///
+    /// # Errors
+    /// If the configuration file exists but cannot be read or is not valid.
+    pub fn with_config_file(self) -> Result<Self, OpenEditorError> {
+        Ok(self.with_config(&Config::load()?))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl EditorCallBuilder {
+    /// Edits a value serialized in `format` and returns the edited value.
+    ///
+    /// The temporary file gets the extension of the format, and the editor is reopened with
+    /// the error at the top until the content can be deserialized, see `edit_until_valid`.
+    ///
+    /// # Errors
+    /// If the value cannot be serialized, if the editor call fails, or if the temporary file
+    /// cannot be read or cleaned up, or if the edit is aborted.
+    pub fn edit_value<T: serde::Serialize + serde::de::DeserializeOwned>(
+        &self,
+        value: &T,
+        format: Format,
+    ) -> Result<T, OpenEditorError> {
+        let text = format.serialize(value)?;
+        self.clone()
+            .with_temp_file_suffix(format.extension())
+            .with_comment_prefix(format.comment_prefix())
+            .edit_until_valid(&text, |text| -> Result<T, crate::ValidationError> {
+                format.deserialize(text)
+            })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl EditorCallBuilder {
+    /// Asynchronous version of [`EditorCallBuilder::open_editor`].
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up.
+    pub async fn open_editor_async(&self) -> Result<String, OpenEditorError> {
+        self.edit_string_async("").await
+    }
+    /// Asynchronous version of [`EditorCallBuilder::edit_string_mut`].
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up.
+    pub async fn edit_string_mut_async(&self, string: &mut String) -> Result<(), OpenEditorError> {
+        *string = self.edit_string_async(string).await?;
+        Ok(())
+    }
+    /// Asynchronous version of [`EditorCallBuilder::edit_string`].
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
+    /// or if the edit is aborted.
+    pub async fn edit_string_async(&self, string: &str) -> Result<String, OpenEditorError> {
+        let content = self.edit_contents_async(string).await?;
+        self.edit_outcome(string, &content)?;
+        Ok(content)
+    }
+    /// Asynchronous version of [`EditorCallBuilder::edit_string_with_outcome`].
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
+    /// or if the edit is aborted.
+    pub async fn edit_string_with_outcome_async(
+        &self,
+        string: &str,
+    ) -> Result<EditOutcome, OpenEditorError> {
+        let content = self.edit_contents_async(string).await?;
+        self.edit_outcome(string, &content)
+    }
+    /// Asynchronous version of [`EditorCallBuilder::edit_until_valid`].
+    ///
+    /// # Errors
+    /// If the editor call fails, or if the temporary file cannot be read or cleaned up,
+    /// or if the edit is aborted.
+    pub async fn edit_until_valid_async<T, E: Display + 'static>(
+        &self,
+        string: &str,
+        mut validate: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<T, OpenEditorError> {
+        let mut validation = Validation::new(string);
+        loop {
+            let (builder, original) = validation.next_edit(self);
+            let content = builder.edit_contents_async(&original).await?;
+            if let Some(value) = validation.check(self, &original, &content, &mut validate)? {
+                return Ok(value);
+            }
+        }
+    }
+    /// Asynchronous version of [`EditorCallBuilder::edit_contents`].
+    async fn edit_contents_async(&self, string: &str) -> Result<String, OpenEditorError> {
+        let file = self.create_backing_file()?;
+        let content = async {
+            tokio::fs::write(file.path(), self.cleanup.prepare(string))
+                .await
+                .map_err(OpenEditorError::file_manipulation(file.path()))?;
+            self.open_file_async(file.path()).await?;
+            tokio::fs::read_to_string(file.path())
+                .await
+                .map_err(OpenEditorError::file_manipulation(file.path()))
+        }
+        .await;
+        self.finish_edit(file, content)
+    }
+    /// Asynchronous version of [`EditorCallBuilder::open_file`].
+    ///
+    /// # Errors
+    /// This function will return an error if the editor call fails.
+    pub async fn open_file_async(&self, file_path: &Path) -> Result<(), OpenEditorError> {
+        self.editor_call(file_path, self.wait)?.call_async().await
+    }
+    /// Asynchronous version of [`EditorCallBuilder::open_files`].
+    ///
+    /// # Errors
+    /// This function will return an error if an editor call fails.
+    pub async fn open_files_async(&self, files: &[FileTarget]) -> Result<(), OpenEditorError> {
+        if let [file] = files {
+            return self.target_call(file, self.wait)?.call_async().await;
+        }
+        if let Some(call) = self.files_call(files, self.wait)? {
+            return call.call_async().await;
+        }
+        for file in files {
+            self.target_call(file, self.wait)?.call_async().await?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "tokio")]
+impl EditorCall {
+    /// Asynchronous version of [`EditorCall::call`].
+    /// # Errors
+    ///
+    /// This function will return an error if the commands fails to execute or if the editor returns a non-zero exit code.
+    pub async fn call_async(&self) -> Result<(), OpenEditorError> {
+        let call = self.context();
+        let mut child = tokio::process::Command::from(self.command(&call)?)
+            .spawn()
+            .map_err(|e| OpenEditorError::CommandFail {
+                call: call.clone(),
+                error: e,
+            })?;
+        if !self.wait {
+            return Ok(());
+        }
+        let (status, stderr) = match child.stderr.take() {
+            Some(stderr) => {
+                wait_capturing_stderr(&mut child, stderr, self.stderr_mode == StderrMode::Tee).await
             }
-            Err(e) => Err(OpenEditorError::CommandFail { error: e }),
+            None => child.wait().await.map(|status| (status, String::new())),
         }
+        .map_err(|e| OpenEditorError::CommandFail {
+            call: call.clone(),
+            error: e,
+        })?;
+        EditorHandle::check_status(&call, status, stderr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_editor_precedence() {
+        let builder = EditorCallBuilder {
+            configured_file_editors: vec![
+                FileRule::new("*.md", "configured"),
+                FileRule::new("*.txt", "configured"),
+            ],
+            ..EditorCallBuilder::new()
+        }
+        .with_file_editor("*.md", "first")
+        .with_file_editor("*", "catch-all")
+        .with_file_editor("README.*", "last");
+        let editor = |path: &str| builder.file_editor(Path::new(path));
+        // The last matching rule wins, and rules set in code come before configured ones
+        assert_eq!(editor("README.md").as_deref(), Some("last"));
+        assert_eq!(editor("notes.md").as_deref(), Some("catch-all"));
+        let configured = EditorCallBuilder {
+            file_editors: vec![],
+            ..builder.clone()
+        };
+        assert_eq!(
+            configured.file_editor(Path::new("notes.txt")).as_deref(),
+            Some("configured")
+        );
+        assert_eq!(configured.file_editor(Path::new("notes.rs")), None);
     }
 }
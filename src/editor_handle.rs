@@ -0,0 +1,155 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Child, ExitStatus},
+};
+
+use crate::{
+    backing_file::BackingFile,
+    cleanup::Cleanup,
+    errors::{EditorCallContext, OpenEditorError},
+    stderr_capture::StderrCapture,
+};
+
+#[derive(Debug)]
+/// A handle to an editor running in the background, returned by the `spawn_*` methods of
+/// [`EditorCallBuilder`](crate::EditorCallBuilder).
+///
+/// Dropping the handle does not stop the editor, but removes the temporary file it was
+/// given, if any, unless it was asked to be kept.
+pub struct EditorHandle {
+    call: EditorCallContext,
+    child: Child,
+    stderr: Option<StderrCapture>,
+    file_path: PathBuf,
+    file: Option<BackingFile>,
+    keep_file: bool,
+    cleanup: Cleanup,
+}
+
+impl EditorHandle {
+    pub(crate) fn new(
+        call: EditorCallContext,
+        child: Child,
+        stderr: Option<StderrCapture>,
+        file_path: PathBuf,
+        file: Option<BackingFile>,
+        keep_file: bool,
+        cleanup: Cleanup,
+    ) -> Self {
+        Self {
+            call,
+            child,
+            stderr,
+            file_path,
+            file,
+            keep_file,
+            cleanup,
+        }
+    }
+    /// Returns the OS-assigned process identifier of the editor.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+    /// Returns the path of the file opened in the editor.
+    #[must_use]
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+    /// Waits for the editor to exit.
+    ///
+    /// # Errors
+    /// Returns an error if the editor cannot be waited on or if it returns a non-zero exit code.
+    /// The error contains the stderr of the editor if it was captured.
+    pub fn wait(&mut self) -> Result<(), OpenEditorError> {
+        let status = self
+            .child
+            .wait()
+            .map_err(|e| OpenEditorError::CommandFail {
+                call: self.call.clone(),
+                error: e,
+            })?;
+        let stderr = self
+            .stderr
+            .take()
+            .map(StderrCapture::finish)
+            .unwrap_or_default();
+        Self::check_status(&self.call, status, stderr)
+    }
+    /// Checks whether the editor has exited without blocking.
+    /// Returns `Ok(None)` if it is still running.
+    ///
+    /// # Errors
+    /// Returns an error if the status of the editor cannot be checked.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>, OpenEditorError> {
+        self.child
+            .try_wait()
+            .map_err(|e| OpenEditorError::CommandFail {
+                call: self.call.clone(),
+                error: e,
+            })
+    }
+    /// Kills the editor.
+    ///
+    /// # Errors
+    /// Returns an error if the editor cannot be killed.
+    pub fn kill(&mut self) -> Result<(), OpenEditorError> {
+        self.child.kill().map_err(|e| OpenEditorError::CommandFail {
+            call: self.call.clone(),
+            error: e,
+        })
+    }
+    /// Reads the current content of the file opened in the editor.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read.
+    pub fn read_contents(&self) -> Result<String, OpenEditorError> {
+        std::fs::read_to_string(&self.file_path)
+            .map_err(OpenEditorError::file_manipulation(&self.file_path))
+    }
+    /// Waits for the editor to exit and returns the content of the file, cleaned up as set
+    /// with [`EditorCallBuilder::with_cleanup`](crate::EditorCallBuilder::with_cleanup).
+    /// The temporary file is then cleaned up unless it was asked to be kept.
+    ///
+    /// # Errors
+    /// Returns an error if the editor fails, or if the file cannot be read or cleaned up.
+    pub fn wait_for_contents(mut self) -> Result<String, OpenEditorError> {
+        let result = self
+            .wait()
+            .and_then(|()| self.read_contents())
+            .map(|content| self.cleanup.apply(&content));
+        let cleanup = match self.file.take() {
+            Some(file) if self.keep_file => file.keep(),
+            Some(file) => file.close(),
+            None => Ok(()),
+        };
+        let result = result?;
+        cleanup?;
+        Ok(result)
+    }
+    /// Converts the exit status of the editor into a result.
+    pub(crate) fn check_status(
+        call: &EditorCallContext,
+        status: ExitStatus,
+        stderr: String,
+    ) -> Result<(), OpenEditorError> {
+        if status.success() {
+            Ok(())
+        } else {
+            Err(OpenEditorError::EditorCallError {
+                call: call.clone(),
+                exit_code: status.code(),
+                stderr,
+            })
+        }
+    }
+}
+impl Drop for EditorHandle {
+    fn drop(&mut self) {
+        if self.keep_file
+            && let Some(file) = self.file.take()
+        {
+            let _ = file.keep();
+        }
+    }
+}
@@ -1,99 +1,767 @@
 use std::{fmt::Display, path::Path};
 
+use crate::{
+    arg_template::ArgTemplate,
+    editor_profile::{EditorInterface, EditorProfile, WaitBehavior},
+    file_target::FileTarget,
+};
+
+/// The editors this crate knows how to invoke. New kinds may be added in minor releases.
 #[derive(Default, Debug, Clone)]
+#[non_exhaustive]
 pub enum EditorKind {
     // CLI
     Vi,
     Vim,
     Nvim,
     Emacs,
+    EmacsClient,
     Nano,
     Pico,
     Helix,
     Kakoune,
+    Micro,
+    Mcedit,
+    Joe,
+    Ne,
+    Jed,
+    Vis,
+    Amp,
     // GUI
     Code,
+    VSCodium,
+    Cursor,
     Gvim,
+    NvimGui(NvimGui),
+    Sublime,
+    Zed,
+    Kate,
+    Gedit,
+    Geany,
+    TextMate,
+    JetBrains(JetBrainsIde),
+    Lapce,
+    Notepadqq,
+    /// An editor unknown to this crate, with the arguments to pass to it.
+    Custom(ArgTemplate),
     #[default]
     UnknownEditor,
 }
 
+/// The JetBrains IDEs, which share the same command line interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JetBrainsIde {
+    Idea,
+    CLion,
+    PyCharm,
+    WebStorm,
+    GoLand,
+    RustRover,
+    PhpStorm,
+    RubyMine,
+    Rider,
+    DataGrip,
+}
+impl JetBrainsIde {
+    const ALL: [JetBrainsIde; 10] = [
+        JetBrainsIde::Idea,
+        JetBrainsIde::CLion,
+        JetBrainsIde::PyCharm,
+        JetBrainsIde::WebStorm,
+        JetBrainsIde::GoLand,
+        JetBrainsIde::RustRover,
+        JetBrainsIde::PhpStorm,
+        JetBrainsIde::RubyMine,
+        JetBrainsIde::Rider,
+        JetBrainsIde::DataGrip,
+    ];
+}
+impl JetBrainsIde {
+    /// Gets the name of the program launching the IDE.
+    #[must_use]
+    pub fn program_name(self) -> &'static str {
+        match self {
+            JetBrainsIde::Idea => "idea",
+            JetBrainsIde::CLion => "clion",
+            JetBrainsIde::PyCharm => "pycharm",
+            JetBrainsIde::WebStorm => "webstorm",
+            JetBrainsIde::GoLand => "goland",
+            JetBrainsIde::RustRover => "rustrover",
+            JetBrainsIde::PhpStorm => "phpstorm",
+            JetBrainsIde::RubyMine => "rubymine",
+            JetBrainsIde::Rider => "rider",
+            JetBrainsIde::DataGrip => "datagrip",
+        }
+    }
+}
+impl Display for JetBrainsIde {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.program_name())
+    }
+}
+
+/// The graphical front-ends of Neovim, which pass the arguments after `--` to `nvim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NvimGui {
+    NvimQt,
+    NvimGtk,
+    Neovide,
+}
+impl NvimGui {
+    /// Gets the name of the program of the front-end.
+    #[must_use]
+    pub fn program_name(self) -> &'static str {
+        match self {
+            NvimGui::NvimQt => "nvim-qt",
+            NvimGui::NvimGtk => "nvim-gtk",
+            NvimGui::Neovide => "neovide",
+        }
+    }
+    /// Gets the flag keeping the front-end in the foreground.
+    fn no_fork_flag(self) -> &'static str {
+        match self {
+            NvimGui::NvimQt => "--nofork",
+            NvimGui::NvimGtk | NvimGui::Neovide => "--no-fork",
+        }
+    }
+}
+impl Display for NvimGui {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.program_name())
+    }
+}
+
 impl From<String> for EditorKind {
     /// Convert a string to an [`EditorKind`].
     fn from(value: String) -> Self {
         match value.as_str() {
-            "vi" => EditorKind::Vi,
+            "vi" | "nvi" => EditorKind::Vi,
             "vim" => EditorKind::Vim,
-            "nvim" => EditorKind::Nvim,
+            "nvim" | "neovim" => EditorKind::Nvim,
             "emacs" => EditorKind::Emacs,
+            "emacsclient" => EditorKind::EmacsClient,
             "nano" => EditorKind::Nano,
             "pico" => EditorKind::Pico,
-            "hx" => EditorKind::Helix,
-            "kak" => EditorKind::Kakoune,
-            "code" | "vscode" => EditorKind::Code,
+            "hx" | "helix" => EditorKind::Helix,
+            "kak" | "kakoune" => EditorKind::Kakoune,
+            "micro" => EditorKind::Micro,
+            "mcedit" => EditorKind::Mcedit,
+            "joe" => EditorKind::Joe,
+            "ne" => EditorKind::Ne,
+            "jed" => EditorKind::Jed,
+            "vis" => EditorKind::Vis,
+            "amp" => EditorKind::Amp,
+            "code" | "vscode" | "code-oss" => EditorKind::Code,
+            "codium" | "vscodium" => EditorKind::VSCodium,
+            "cursor" => EditorKind::Cursor,
             "gvim" => EditorKind::Gvim,
-            _ => EditorKind::UnknownEditor,
+            "nvim-qt" => EditorKind::NvimGui(NvimGui::NvimQt),
+            "nvim-gtk" => EditorKind::NvimGui(NvimGui::NvimGtk),
+            "neovide" => EditorKind::NvimGui(NvimGui::Neovide),
+            "subl" | "sublime_text" | "sublime-text" => EditorKind::Sublime,
+            "zed" | "zeditor" => EditorKind::Zed,
+            "kate" => EditorKind::Kate,
+            "gedit" => EditorKind::Gedit,
+            "geany" => EditorKind::Geany,
+            "mate" => EditorKind::TextMate,
+            "lapce" => EditorKind::Lapce,
+            "notepadqq" => EditorKind::Notepadqq,
+            name => JetBrainsIde::ALL
+                .into_iter()
+                .find(|ide| ide.to_string() == name)
+                .map_or(EditorKind::UnknownEditor, EditorKind::JetBrains),
         }
     }
 }
-/// Get Editor specific arguments for opening a file at a specific line and column.
+/// The default value of vim's `tabpagemax` option, the number of tabs opened by `-p`.
+const VIM_TAB_PAGE_MAX: usize = 10;
+
+/// Suffixes of the builds of an editor, such as `code-insiders` or `emacs-nox`.
+const VARIANT_SUFFIXES: [&str; 9] = [
+    "-insiders",
+    "-oss",
+    "-qt",
+    "-gtk",
+    "-gtk3",
+    "-pgtk",
+    "-lucid",
+    "-nox",
+    "-tiny",
+];
+
 impl EditorKind {
-    pub(crate) fn get_editor_args(
-        &self,
-        file_path: &Path,
-        wait: bool,
-        line: usize,
-        column: usize,
-    ) -> Vec<String> {
+    /// Gets the name of the program of the editor, or `None` for [`EditorKind::Custom`] and
+    /// [`EditorKind::UnknownEditor`].
+    #[must_use]
+    pub fn program_name(&self) -> Option<&'static str> {
+        let name = match self {
+            EditorKind::Vi => "vi",
+            EditorKind::Vim => "vim",
+            EditorKind::Nvim => "nvim",
+            EditorKind::Emacs => "emacs",
+            EditorKind::EmacsClient => "emacsclient",
+            EditorKind::Nano => "nano",
+            EditorKind::Pico => "pico",
+            EditorKind::Helix => "hx",
+            EditorKind::Kakoune => "kak",
+            EditorKind::Micro => "micro",
+            EditorKind::Mcedit => "mcedit",
+            EditorKind::Joe => "joe",
+            EditorKind::Ne => "ne",
+            EditorKind::Jed => "jed",
+            EditorKind::Vis => "vis",
+            EditorKind::Amp => "amp",
+            EditorKind::Code => "code",
+            EditorKind::VSCodium => "codium",
+            EditorKind::Cursor => "cursor",
+            EditorKind::Gvim => "gvim",
+            EditorKind::Sublime => "subl",
+            EditorKind::Zed => "zed",
+            EditorKind::Kate => "kate",
+            EditorKind::Gedit => "gedit",
+            EditorKind::Geany => "geany",
+            EditorKind::TextMate => "mate",
+            EditorKind::Lapce => "lapce",
+            EditorKind::Notepadqq => "notepadqq",
+            EditorKind::JetBrains(ide) => ide.program_name(),
+            EditorKind::NvimGui(gui) => gui.program_name(),
+            EditorKind::Custom(_) | EditorKind::UnknownEditor => return None,
+        };
+        Some(name)
+    }
+    /// Detects the kind of an editor from the path of its binary.
+    ///
+    /// Directories, executable extensions (`.exe`, `.sh`...), variant suffixes (`vim.basic`,
+    /// `emacs-nox`, `code-insiders`) and version numbers (`emacs-29.1`) are ignored. If the name
+    /// is not recognized, symbolic links are followed, so that `/usr/bin/editor` is detected
+    /// through the alternatives system.
+    #[must_use]
+    pub fn from_binary_path(binary_path: &Path) -> Self {
+        let kind = Self::from_binary_name(binary_path);
+        if !matches!(kind, EditorKind::UnknownEditor) {
+            return kind;
+        }
+        std::fs::canonicalize(binary_path)
+            .map(|target| Self::from_binary_name(&target))
+            .unwrap_or(kind)
+    }
+    /// Detects the kind of an editor from the file name of its binary, ignoring variants.
+    fn from_binary_name(binary_path: &Path) -> Self {
+        let Some(name) = binary_path.file_name().and_then(|name| name.to_str()) else {
+            return EditorKind::UnknownEditor;
+        };
+        let name = name.to_lowercase();
+        let mut name = [".exe", ".cmd", ".bat", ".sh"]
+            .iter()
+            .find_map(|extension| name.strip_suffix(extension))
+            .unwrap_or(&name);
+        loop {
+            let kind = EditorKind::from(name.to_string());
+            let stripped = Self::strip_variant(name);
+            if !matches!(kind, EditorKind::UnknownEditor) || stripped == name {
+                return kind;
+            }
+            name = stripped;
+        }
+    }
+    /// Removes one level of variant from an editor name: what follows a dot (`vim.basic`),
+    /// a version number (`emacs-29`, `idea64`), or a known suffix (`emacs-nox`).
+    ///
+    /// Other dash suffixes are kept, since they usually name another program (`kak-lsp`,
+    /// `mate-terminal`).
+    fn strip_variant(name: &str) -> &str {
+        if let Some((base, _)) = name.split_once('.') {
+            return base;
+        }
+        let without_version = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if without_version != name {
+            return without_version.trim_end_matches(['-', '_']);
+        }
+        VARIANT_SUFFIXES
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix))
+            .unwrap_or(name)
+    }
+}
+impl EditorProfile for EditorKind {
+    /// Get Editor specific arguments for opening a file at a specific line and column,
+    /// regardless of waiting.
+    fn file_args(&self, file_path: &Path, line: usize, column: usize) -> Vec<String> {
         let path = file_path.to_string_lossy().into_owned();
         match self {
-            EditorKind::Emacs => {
+            EditorKind::Emacs | EditorKind::EmacsClient | EditorKind::Micro => {
                 vec![format!("+{}:{}", line, column), path]
             }
-            EditorKind::Nano | EditorKind::Pico => {
+            EditorKind::Nano | EditorKind::Pico | EditorKind::Ne => {
                 vec![format!("+{},{}", line, column), path]
             }
-            EditorKind::Helix => {
+            EditorKind::Helix | EditorKind::Sublime | EditorKind::Zed | EditorKind::Lapce => {
                 vec![format!("{}:{}:{}", path, line, column)]
             }
             EditorKind::Kakoune => {
                 vec![format!("{}", path), format!("+{}:{}", line, column)]
             }
-            EditorKind::Code => [
-                if wait { vec!["-w".to_string()] } else { vec![] },
-                vec!["--goto".to_string()],
-                vec![format!("{}:{}:{}", path, line, column)],
-            ]
-            .concat(),
-            EditorKind::Gvim | EditorKind::Vi | EditorKind::Vim | EditorKind::Nvim => [
-                vec![format!("+{}", line,)],
-                if wait {
-                    vec![]
+            EditorKind::Code | EditorKind::VSCodium | EditorKind::Cursor => vec![
+                "--goto".to_string(),
+                format!("{}:{}:{}", path, line, column),
+            ],
+            EditorKind::Gedit => vec![format!("+{}:{}", line, column), path],
+            EditorKind::TextMate => vec!["-l".to_string(), format!("{}:{}", line, column), path],
+            EditorKind::Kate | EditorKind::Geany | EditorKind::JetBrains(_) => vec![
+                "--line".to_string(),
+                line.to_string(),
+                "--column".to_string(),
+                column.to_string(),
+                path,
+            ],
+            // The column is reached by moving right from the start of the line, which counts
+            // characters rather than bytes, so multibyte characters and tabs count as one column.
+            EditorKind::Gvim | EditorKind::Vim | EditorKind::Nvim => [
+                vec![format!("+{line}")],
+                if column > 1 {
+                    vec![format!("+normal! 0{}l", column - 1)]
                 } else {
-                    vec!["--nofork".to_string()]
+                    vec![]
                 },
                 vec![path],
             ]
             .concat(),
+            // The front-end passes the arguments after `--` to nvim.
+            EditorKind::NvimGui(_) => [
+                vec!["--".to_string()],
+                EditorKind::Nvim.file_args(file_path, line, column),
+            ]
+            .concat(),
+            // POSIX vi only supports ex commands, so the column cannot be set.
+            EditorKind::Vi | EditorKind::Mcedit | EditorKind::Joe | EditorKind::Vis => {
+                vec![format!("+{line}"), path]
+            }
+            // The line is set after the file is loaded.
+            EditorKind::Jed => vec![path, "-g".to_string(), line.to_string()],
 
-            EditorKind::UnknownEditor => vec![path],
+            EditorKind::Amp
+            | EditorKind::Notepadqq
+            | EditorKind::Custom(_)
+            | EditorKind::UnknownEditor => vec![path],
+        }
+    }
+    fn wait_behavior(&self) -> WaitBehavior {
+        match self {
+            EditorKind::Code
+            | EditorKind::VSCodium
+            | EditorKind::Cursor
+            | EditorKind::Sublime
+            | EditorKind::TextMate => WaitBehavior::ForksUnless("-w"),
+            EditorKind::Gvim => WaitBehavior::ForksUnless("-f"),
+            EditorKind::NvimGui(gui) => WaitBehavior::ForksUnless(gui.no_fork_flag()),
+            EditorKind::Zed | EditorKind::Gedit | EditorKind::JetBrains(_) | EditorKind::Lapce => {
+                WaitBehavior::ForksUnless("--wait")
+            }
+            EditorKind::Kate => WaitBehavior::ForksUnless("--block"),
+            // A new instance runs until its window is closed, instead of handing the file over
+            EditorKind::Geany => WaitBehavior::ForksUnless("--new-instance"),
+            EditorKind::EmacsClient => WaitBehavior::BlocksUnless("--no-wait"),
+            EditorKind::Notepadqq => WaitBehavior::Forks,
+            EditorKind::Vi
+            | EditorKind::Vim
+            | EditorKind::Nvim
+            | EditorKind::Emacs
+            | EditorKind::Nano
+            | EditorKind::Pico
+            | EditorKind::Helix
+            | EditorKind::Kakoune
+            | EditorKind::Micro
+            | EditorKind::Mcedit
+            | EditorKind::Joe
+            | EditorKind::Ne
+            | EditorKind::Jed
+            | EditorKind::Vis
+            | EditorKind::Amp
+            | EditorKind::UnknownEditor => WaitBehavior::Blocks,
+            // The `{wait}` placeholder is handled by the template
+            EditorKind::Custom(_) => WaitBehavior::Blocks,
+        }
+    }
+    fn interface(&self) -> EditorInterface {
+        match self {
+            EditorKind::Vi
+            | EditorKind::Vim
+            | EditorKind::Nvim
+            | EditorKind::Nano
+            | EditorKind::Pico
+            | EditorKind::Helix
+            | EditorKind::Kakoune
+            | EditorKind::Micro
+            | EditorKind::Mcedit
+            | EditorKind::Joe
+            | EditorKind::Ne
+            | EditorKind::Jed
+            | EditorKind::Vis
+            | EditorKind::Amp => EditorInterface::Terminal,
+            EditorKind::Emacs | EditorKind::EmacsClient => EditorInterface::Adaptive,
+            EditorKind::Code
+            | EditorKind::VSCodium
+            | EditorKind::Cursor
+            | EditorKind::Gvim
+            | EditorKind::NvimGui(_)
+            | EditorKind::Sublime
+            | EditorKind::Zed
+            | EditorKind::Kate
+            | EditorKind::Gedit
+            | EditorKind::Geany
+            | EditorKind::TextMate
+            | EditorKind::JetBrains(_)
+            | EditorKind::Lapce
+            | EditorKind::Notepadqq => EditorInterface::Graphical,
+            EditorKind::Custom(template) => template.interface(),
+            EditorKind::UnknownEditor => EditorInterface::Unknown,
+        }
+    }
+    fn editor_args(&self, file_path: &Path, wait: bool, line: usize, column: usize) -> Vec<String> {
+        if let EditorKind::Custom(template) = self {
+            return template.editor_args(file_path, wait, line, column);
         }
+        [
+            self.wait_behavior().args(wait),
+            self.file_args(file_path, line, column),
+        ]
+        .concat()
+    }
+    fn multi_file_args(&self, files: &[FileTarget], wait: bool) -> Option<Vec<String>> {
+        let path = |file: &FileTarget| file.path.to_string_lossy().into_owned();
+        let args = match self {
+            // Open each file in its own tab, then move the cursor in the tabs of the files with
+            // a position and go back to the first one.
+            EditorKind::Vim | EditorKind::Nvim | EditorKind::Gvim => {
+                let mut args = vec![];
+                // Only 10 tabs are opened by default, the other files would be left hidden
+                if files.len() > VIM_TAB_PAGE_MAX {
+                    args.push("--cmd".to_string());
+                    args.push(format!("set tabpagemax={}", files.len()));
+                }
+                args.push("-p".to_string());
+                args.extend(files.iter().map(path));
+                let moves = files
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, file)| {
+                        let (line, column) = file.position()?;
+                        let mut command = format!("{}tabnext | {line}", index + 1);
+                        if column > 1 {
+                            command.push_str(&format!(" | execute 'normal! 0{}l'", column - 1));
+                        }
+                        Some(command)
+                    })
+                    .collect::<Vec<_>>();
+                if !moves.is_empty() {
+                    args.push("-c".to_string());
+                    args.push(format!("{} | 1tabnext", moves.join(" | ")));
+                }
+                args
+            }
+            EditorKind::Emacs | EditorKind::EmacsClient | EditorKind::Micro => files
+                .iter()
+                .flat_map(|file| {
+                    let position = file
+                        .position()
+                        .map(|(line, column)| format!("+{line}:{column}"));
+                    position.into_iter().chain([path(file)])
+                })
+                .collect(),
+            EditorKind::Nano => files
+                .iter()
+                .flat_map(|file| {
+                    let position = file
+                        .position()
+                        .map(|(line, column)| format!("+{line},{column}"));
+                    position.into_iter().chain([path(file)])
+                })
+                .collect(),
+            EditorKind::Helix | EditorKind::Sublime | EditorKind::Zed | EditorKind::Lapce => files
+                .iter()
+                .map(|file| match file.position() {
+                    Some((line, column)) => format!("{}:{line}:{column}", path(file)),
+                    None => path(file),
+                })
+                .collect(),
+            // `--goto` applies to every path, and paths without a position open at the start.
+            EditorKind::Code | EditorKind::VSCodium | EditorKind::Cursor => {
+                std::iter::once("--goto".to_string())
+                    .chain(files.iter().map(|file| match file.position() {
+                        Some((line, column)) => format!("{}:{line}:{column}", path(file)),
+                        None => path(file),
+                    }))
+                    .collect()
+            }
+            // These only take a position for all the files, so files with their own position
+            // are opened one after the other.
+            EditorKind::Kakoune
+            | EditorKind::Kate
+            | EditorKind::Gedit
+            | EditorKind::Geany
+            | EditorKind::TextMate
+            | EditorKind::JetBrains(_)
+                if files.iter().any(|file| file.position().is_some()) =>
+            {
+                return None;
+            }
+            EditorKind::Kakoune
+            | EditorKind::Kate
+            | EditorKind::Gedit
+            | EditorKind::Geany
+            | EditorKind::TextMate
+            | EditorKind::JetBrains(_) => files.iter().map(path).collect(),
+            EditorKind::Custom(template) => return template.multi_file_args(files, wait),
+            _ => return None,
+        };
+        Some([self.wait_behavior().args(wait), args].concat())
+    }
+    fn diff_args(&self, left: &Path, right: &Path, wait: bool) -> Option<Vec<String>> {
+        let (left, right) = (
+            left.to_string_lossy().into_owned(),
+            right.to_string_lossy().into_owned(),
+        );
+        let args = match self {
+            EditorKind::Vim | EditorKind::Nvim | EditorKind::Gvim => {
+                vec!["-d".to_string(), left, right]
+            }
+            EditorKind::Code | EditorKind::VSCodium | EditorKind::Cursor => {
+                vec!["--diff".to_string(), left, right]
+            }
+            EditorKind::Emacs => vec![
+                "--eval".to_string(),
+                format!("(ediff-files {left:?} {right:?})"),
+            ],
+            EditorKind::JetBrains(_) => vec!["diff".to_string(), left, right],
+            _ => return None,
+        };
+        Some([self.wait_behavior().args(wait), args].concat())
     }
 }
 impl Display for EditorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            EditorKind::Vi => write!(f, "vi"),
-            EditorKind::Vim => write!(f, "vim"),
-            EditorKind::Nvim => write!(f, "nvim"),
-            EditorKind::Emacs => write!(f, "emacs"),
-            EditorKind::Nano => write!(f, "nano"),
-            EditorKind::Pico => write!(f, "pico"),
-            EditorKind::Helix => write!(f, "hx"),
-            EditorKind::Kakoune => write!(f, "kak"),
-            EditorKind::Code => write!(f, "code"),
-            EditorKind::Gvim => write!(f, "gvim"),
-            EditorKind::UnknownEditor => Err(std::fmt::Error),
+        match (self, self.program_name()) {
+            (_, Some(name)) => f.write_str(name),
+            (EditorKind::Custom(_), None) => write!(f, "custom editor"),
+            (_, None) => write!(f, "unknown editor"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIM_KINDS: [EditorKind; 3] = [EditorKind::Vim, EditorKind::Nvim, EditorKind::Gvim];
+
+    fn args(kind: &EditorKind, wait: bool, line: usize, column: usize) -> Vec<String> {
+        kind.editor_args(Path::new("file.txt"), wait, line, column)
+    }
+
+    #[test]
+    fn vim_first_column_has_no_normal_command() {
+        for kind in &VIM_KINDS {
+            assert_eq!(args(kind, false, 3, 1), ["+3", "file.txt"], "{kind}");
+        }
+    }
+
+    #[test]
+    fn vim_column_moves_right_from_line_start() {
+        for kind in &VIM_KINDS {
+            assert_eq!(
+                args(kind, false, 3, 5),
+                ["+3", "+normal! 04l", "file.txt"],
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn vim_position_comes_after_wait_flag_and_before_path() {
+        assert_eq!(
+            args(&EditorKind::Gvim, true, 12, 2),
+            ["-f", "+12", "+normal! 01l", "file.txt"]
+        );
+        for kind in [EditorKind::Vim, EditorKind::Nvim] {
+            assert_eq!(
+                args(&kind, true, 12, 2),
+                ["+12", "+normal! 01l", "file.txt"],
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn new_kinds_file_arguments() {
+        let cases: [(EditorKind, &[&str], &[&str]); 19] = [
+            (EditorKind::Micro, &[], &["+3:5", "file.txt"]),
+            (EditorKind::Sublime, &["-w"], &["file.txt:3:5"]),
+            (EditorKind::Zed, &["--wait"], &["file.txt:3:5"]),
+            (
+                EditorKind::Kate,
+                &["--block"],
+                &["--line", "3", "--column", "5", "file.txt"],
+            ),
+            (EditorKind::Gedit, &["--wait"], &["+3:5", "file.txt"]),
+            (
+                EditorKind::Geany,
+                &["--new-instance"],
+                &["--line", "3", "--column", "5", "file.txt"],
+            ),
+            (EditorKind::Mcedit, &[], &["+3", "file.txt"]),
+            (EditorKind::Joe, &[], &["+3", "file.txt"]),
+            (EditorKind::Ne, &[], &["+3,5", "file.txt"]),
+            (EditorKind::Jed, &[], &["file.txt", "-g", "3"]),
+            (EditorKind::Vis, &[], &["+3", "file.txt"]),
+            (EditorKind::Amp, &[], &["file.txt"]),
+            (EditorKind::TextMate, &["-w"], &["-l", "3:5", "file.txt"]),
+            (EditorKind::VSCodium, &["-w"], &["--goto", "file.txt:3:5"]),
+            (EditorKind::Cursor, &["-w"], &["--goto", "file.txt:3:5"]),
+            (EditorKind::Lapce, &["--wait"], &["file.txt:3:5"]),
+            (EditorKind::Notepadqq, &[], &["file.txt"]),
+            (EditorKind::EmacsClient, &[], &["+3:5", "file.txt"]),
+            (EditorKind::Pico, &[], &["+3,5", "file.txt"]),
+        ];
+        for (kind, wait_args, file_args) in cases {
+            assert_eq!(
+                args(&kind, true, 3, 5),
+                [wait_args, file_args].concat(),
+                "{kind} waiting"
+            );
+        }
+        // Only emacsclient is given a flag not to wait, the others are simply not waited for
+        for (kind, file_args) in [
+            (
+                EditorKind::EmacsClient,
+                ["--no-wait", "+3:5", "file.txt"].as_slice(),
+            ),
+            (EditorKind::Sublime, &["file.txt:3:5"]),
+            (
+                EditorKind::Geany,
+                &["--line", "3", "--column", "5", "file.txt"],
+            ),
+            (EditorKind::Notepadqq, &["file.txt"]),
+        ] {
+            assert_eq!(args(&kind, false, 3, 5), file_args, "{kind} not waiting");
+        }
+    }
+
+    #[test]
+    fn jetbrains_file_arguments() {
+        for ide in JetBrainsIde::ALL {
+            let kind = EditorKind::JetBrains(ide);
+            let file_args = ["--line", "3", "--column", "5", "file.txt"];
+            assert_eq!(
+                args(&kind, true, 3, 5),
+                [["--wait"].as_slice(), &file_args].concat(),
+                "{kind}"
+            );
+            assert_eq!(args(&kind, false, 3, 5), file_args, "{kind}");
+        }
+    }
+
+    #[test]
+    fn variant_suffixes_are_ignored() {
+        for (name, kind) in [
+            ("code-insiders", "code"),
+            ("emacs-nox", "emacs"),
+            ("emacs-29.1", "emacs"),
+            ("vim.basic", "vim"),
+            ("vim-tiny", "vim"),
+            ("idea64.exe", "idea"),
+            ("nvim-qt", "nvim-qt"),
+            ("neovide", "neovide"),
+        ] {
+            let detected = EditorKind::from_binary_name(Path::new(name));
+            assert_eq!(detected.to_string(), kind, "{name}");
+        }
+    }
+
+    #[test]
+    fn other_programs_are_not_detected() {
+        for name in [
+            "mate-terminal",
+            "kak-lsp",
+            "vi-wrapper",
+            "ne-foo",
+            "code-server",
+        ] {
+            let detected = EditorKind::from_binary_name(Path::new(name));
+            assert!(
+                matches!(detected, EditorKind::UnknownEditor),
+                "{name}: {detected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn nvim_front_ends_are_graphical() {
+        let kind = EditorKind::from_binary_name(Path::new("nvim-qt"));
+        assert_eq!(kind.interface(), EditorInterface::Graphical);
+        assert_eq!(
+            args(&kind, true, 3, 5),
+            ["--nofork", "--", "+3", "+normal! 04l", "file.txt"]
+        );
+        assert_eq!(args(&kind, false, 3, 1), ["--", "+3", "file.txt"]);
+    }
+
+    #[test]
+    fn multi_file_positions_are_kept() {
+        let files = [
+            FileTarget::new("a.rs").at_line(3).at_column(5),
+            FileTarget::new("b.rs"),
+        ];
+        assert_eq!(
+            EditorKind::Vim.multi_file_args(&files, true).unwrap(),
+            [
+                "-p",
+                "a.rs",
+                "b.rs",
+                "-c",
+                "1tabnext | 3 | execute 'normal! 04l' | 1tabnext"
+            ]
+        );
+        assert_eq!(
+            EditorKind::Helix.multi_file_args(&files, true).unwrap(),
+            ["a.rs:3:5", "b.rs"]
+        );
+        assert_eq!(
+            EditorKind::Code.multi_file_args(&files, false).unwrap(),
+            ["--goto", "a.rs:3:5", "b.rs"]
+        );
+        assert_eq!(
+            EditorKind::Nano.multi_file_args(&files, true).unwrap(),
+            ["+3,5", "a.rs", "b.rs"]
+        );
+    }
+
+    #[test]
+    fn multi_file_single_position_kinds_fall_back_with_positions() {
+        let plain = [FileTarget::new("a.rs"), FileTarget::new("b.rs")];
+        let positioned = [FileTarget::new("a.rs").at_line(3), FileTarget::new("b.rs")];
+        for kind in [
+            EditorKind::Kakoune,
+            EditorKind::Kate,
+            EditorKind::Gedit,
+            EditorKind::Geany,
+            EditorKind::TextMate,
+            EditorKind::JetBrains(JetBrainsIde::Idea),
+        ] {
+            let args = kind.multi_file_args(&plain, false).unwrap();
+            assert_eq!(args, ["a.rs", "b.rs"], "{kind}");
+            assert_eq!(kind.multi_file_args(&positioned, false), None, "{kind}");
         }
     }
+
+    #[test]
+    fn vim_opens_more_than_ten_tabs() {
+        let files = (1..=11)
+            .map(|i| FileTarget::new(format!("{i}.rs")))
+            .collect::<Vec<_>>();
+        let args = EditorKind::Vim.multi_file_args(&files, true).unwrap();
+        assert_eq!(args[..3], ["--cmd", "set tabpagemax=11", "-p"]);
+        let files = &files[..10];
+        let args = EditorKind::Vim.multi_file_args(files, true).unwrap();
+        assert_eq!(args[0], "-p");
+    }
 }
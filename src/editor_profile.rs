@@ -0,0 +1,93 @@
+use std::{fmt::Debug, path::Path};
+
+use crate::file_target::FileTarget;
+
+/// How an editor process behaves regarding the user's editing session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitBehavior {
+    /// Runs in the foreground until the user is done, like terminal editors.
+    /// Not waiting for it simply means not waiting for the process.
+    Blocks,
+    /// Hands the file to another process and returns immediately, with no way to wait.
+    Forks,
+    /// Returns immediately unless given this flag, like `code -w` or `gvim -f`.
+    ForksUnless(&'static str),
+    /// Runs in the foreground unless given this flag, like `emacsclient --no-wait`.
+    BlocksUnless(&'static str),
+}
+impl WaitBehavior {
+    /// Gets the arguments making the editor block until the user is done if `wait` is true,
+    /// or return immediately otherwise.
+    #[must_use]
+    pub fn args(self, wait: bool) -> Vec<String> {
+        match (self, wait) {
+            (WaitBehavior::ForksUnless(flag), true) | (WaitBehavior::BlocksUnless(flag), false) => {
+                vec![flag.to_string()]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Where an editor displays its interface.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum EditorInterface {
+    /// Runs inside the terminal it was launched from.
+    Terminal,
+    /// Opens its own window.
+    #[cfg_attr(feature = "config", serde(alias = "gui"))]
+    Graphical,
+    /// Opens a window when possible and runs inside the terminal otherwise, like emacs.
+    Adaptive,
+    /// Nothing is known about the editor.
+    #[default]
+    Unknown,
+}
+
+/// Describes how to invoke an editor.
+///
+/// It is implemented by [`EditorKind`](crate::EditorKind) and [`ArgTemplate`](crate::ArgTemplate),
+/// and can be implemented to support other editors with [`Editor::new`](crate::Editor::new).
+pub trait EditorProfile: Debug + Send + Sync {
+    /// Gets the arguments for opening a file at a specific line and column, regardless of waiting.
+    fn file_args(&self, file_path: &Path, line: usize, column: usize) -> Vec<String>;
+
+    /// Gets how the editor behaves regarding the user's editing session.
+    fn wait_behavior(&self) -> WaitBehavior {
+        WaitBehavior::Blocks
+    }
+
+    /// Gets where the editor displays its interface.
+    fn interface(&self) -> EditorInterface {
+        EditorInterface::Unknown
+    }
+
+    /// Gets all the arguments for opening a file at a specific line and column, blocking until
+    /// the user is done if `wait` is true.
+    fn editor_args(&self, file_path: &Path, wait: bool, line: usize, column: usize) -> Vec<String> {
+        [
+            self.wait_behavior().args(wait),
+            self.file_args(file_path, line, column),
+        ]
+        .concat()
+    }
+
+    /// Gets the arguments for opening several files in a single session,
+    /// or `None` if the editor does not support it.
+    fn multi_file_args(&self, files: &[FileTarget], wait: bool) -> Option<Vec<String>> {
+        let _ = (files, wait);
+        None
+    }
+
+    /// Gets the arguments for showing the differences between two files,
+    /// or `None` if the editor does not support it.
+    fn diff_args(&self, left: &Path, right: &Path, wait: bool) -> Option<Vec<String>> {
+        let _ = (left, right, wait);
+        None
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    env,
+    io::{IsTerminal, stdin},
+};
+
+use crate::EditorInterface;
+
+/// What the environment the editor is launched from can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Environment {
+    /// Whether windows can be opened, either locally or through X11 forwarding.
+    pub has_display: bool,
+    /// Whether the process runs in an SSH session.
+    pub ssh_session: bool,
+    /// Whether stdin is a terminal that terminal editors can use.
+    pub stdin_is_terminal: bool,
+    /// Whether `TERM` is `dumb`, meaning the terminal cannot run full-screen editors.
+    pub dumb_terminal: bool,
+}
+
+impl Environment {
+    /// Detects the environment of the current process.
+    ///
+    /// On Linux and other Unix systems, a display is available if `DISPLAY` or `WAYLAND_DISPLAY`
+    /// is set. On macOS and Windows, one is assumed outside of SSH sessions.
+    #[must_use]
+    pub fn detect() -> Self {
+        let is_set = |var| env::var_os(var).is_some_and(|value| !value.is_empty());
+        let ssh_session = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+            .into_iter()
+            .any(is_set);
+        let has_display = is_set("DISPLAY")
+            || is_set("WAYLAND_DISPLAY")
+            || (cfg!(any(target_os = "macos", windows)) && !ssh_session);
+        Self {
+            has_display,
+            ssh_session,
+            stdin_is_terminal: stdin().is_terminal(),
+            dumb_terminal: env::var_os("TERM").is_some_and(|term| term == "dumb"),
+        }
+    }
+    /// Whether an editor with this interface can be used in this environment.
+    /// Editors with an unknown interface are assumed to be usable.
+    #[must_use]
+    pub fn can_run(&self, interface: EditorInterface) -> bool {
+        self.unusable_reason(interface).is_none()
+    }
+    /// Explains why an editor with this interface cannot be used in this environment.
+    pub(crate) fn unusable_reason(&self, interface: EditorInterface) -> Option<&'static str> {
+        match interface {
+            EditorInterface::Terminal if !self.stdin_is_terminal => {
+                Some("needs a terminal but stdin is not one")
+            }
+            EditorInterface::Graphical if !self.has_display => {
+                Some("needs a display but none is available")
+            }
+            EditorInterface::Adaptive if !self.stdin_is_terminal && !self.has_display => {
+                Some("needs a terminal or a display but none is available")
+            }
+            _ => None,
+        }
+    }
+}
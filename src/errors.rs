@@ -1,55 +1,154 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::EditOutcome;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The editor invocation that led to an error, for diagnostics.
+pub struct EditorCallContext {
+    /// Path of the editor binary.
+    pub binary_path: PathBuf,
+    /// Arguments passed to the editor.
+    pub args: Vec<String>,
+    /// File opened in the editor.
+    pub file_path: PathBuf,
+}
+impl Display for EditorCallContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}", self.binary_path.display())?;
+        for arg in &self.args {
+            write!(f, " {}", shell_words::quote(arg))?;
+        }
+        write!(f, "` on {}", self.file_path.display())
+    }
+}
 
 #[derive(Debug)]
 /// Errors that can occur when trying to open an editor.
+///
+/// New variants may be added in minor releases.
+#[non_exhaustive]
 pub enum OpenEditorError {
-    NoEditorFound,
+    NoEditorFound {
+        tried: Vec<String>,
+    },
+    NoUsableEditor {
+        skipped: Vec<String>,
+    },
     EditorCallError {
+        call: EditorCallContext,
         exit_code: Option<i32>,
         stderr: String,
     },
     CommandFail {
+        call: EditorCallContext,
         error: std::io::Error,
     },
     EditorNotFound {
         binary_path: PathBuf,
+        call: EditorCallContext,
     },
     EditorNotExecutable {
         binary_path: PathBuf,
+        call: EditorCallContext,
         error: Option<std::io::Error>,
     },
-    FileManipulationFail(std::io::Error),
-    TempFileCleanupFail(String),
+    FileManipulationFail {
+        file_path: PathBuf,
+        error: std::io::Error,
+    },
+    TempFileCleanupFail {
+        file_path: PathBuf,
+        error: std::io::Error,
+    },
+    InvalidEditorCommand {
+        command: String,
+        error: Option<shell_words::ParseError>,
+    },
+    EditAborted {
+        outcome: EditOutcome,
+    },
+    InvalidConfig {
+        file_path: PathBuf,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    SerializationFail {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+impl OpenEditorError {
+    /// Builds a `FileManipulationFail` error for `file_path`, to be used with `map_err`.
+    pub(crate) fn file_manipulation(file_path: &Path) -> impl FnOnce(std::io::Error) -> Self {
+        let file_path = file_path.to_path_buf();
+        move |error| OpenEditorError::FileManipulationFail { file_path, error }
+    }
 }
 impl Display for OpenEditorError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            OpenEditorError::NoEditorFound => write!(f, "No editor found in the system path."),
-            OpenEditorError::EditorCallError { exit_code, stderr } => write!(
+            OpenEditorError::NoEditorFound { tried } => write!(
                 f,
-                "Editor call failed with exit code {exit_code:?} and stderr: {stderr}"
+                "No editor found in the system path, tried: {}",
+                tried.join(", ")
             ),
-            OpenEditorError::CommandFail { error } => {
-                write!(f, "Command failed: {error:?}")
+            OpenEditorError::NoUsableEditor { skipped } => write!(
+                f,
+                "No editor usable in this environment: {}",
+                skipped.join(", ")
+            ),
+            OpenEditorError::EditorCallError {
+                call,
+                exit_code,
+                stderr,
+            } => write!(
+                f,
+                "Editor call {call} failed with exit code {exit_code:?} and stderr: {stderr}"
+            ),
+            OpenEditorError::CommandFail { call, error } => {
+                write!(f, "Command {call} failed: {error}")
             }
-            OpenEditorError::EditorNotFound { binary_path } => write!(
+            OpenEditorError::EditorNotFound { binary_path, call } => write!(
                 f,
-                "Editor binary not found at path: {}",
+                "Editor binary not found at path: {} for {call}",
                 binary_path.display()
             ),
             OpenEditorError::EditorNotExecutable {
                 error: _,
                 binary_path,
+                call,
             } => write!(
                 f,
-                "Editor binary is not executable at path: {}",
+                "Editor binary is not executable at path: {} for {call}",
                 binary_path.display()
             ),
-            OpenEditorError::FileManipulationFail(error) => {
-                write!(f, "Failed to read file: {error}")
+            OpenEditorError::FileManipulationFail { file_path, error } => {
+                write!(f, "Failed to access file {}: {error}", file_path.display())
             }
-            OpenEditorError::TempFileCleanupFail(filename) => {
-                write!(f, "Failed to clean up temporary file: {filename}")
+            OpenEditorError::TempFileCleanupFail { file_path, error } => {
+                write!(
+                    f,
+                    "Failed to clean up temporary file {}: {error}",
+                    file_path.display()
+                )
+            }
+            OpenEditorError::InvalidEditorCommand { command, error } => match error {
+                Some(error) => write!(f, "Invalid editor command `{command}`: {error}"),
+                None => write!(f, "Invalid editor command `{command}`: no program given"),
+            },
+            OpenEditorError::EditAborted { outcome } => match outcome {
+                EditOutcome::Emptied => write!(f, "Edit aborted: the content is empty"),
+                EditOutcome::Unchanged => write!(f, "Edit aborted: the content was not changed"),
+                EditOutcome::Modified(_) => write!(f, "Edit aborted"),
+            },
+            OpenEditorError::InvalidConfig { file_path, error } => write!(
+                f,
+                "Invalid configuration file {}: {error}",
+                file_path.display()
+            ),
+            OpenEditorError::SerializationFail { error } => {
+                write!(f, "Failed to serialize the value to edit: {error}")
             }
         }
     }
@@ -57,20 +156,34 @@ impl Display for OpenEditorError {
 impl std::error::Error for OpenEditorError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            OpenEditorError::EditorCallError {
-                exit_code: _,
-                stderr: _,
-            } => todo!(),
-            OpenEditorError::CommandFail { error }
-            | OpenEditorError::FileManipulationFail(error) => Some(error),
-            OpenEditorError::EditorNotFound { binary_path: _ } | OpenEditorError::NoEditorFound => {
-                None
+            // The editor itself is the root cause, its stderr is part of the message
+            OpenEditorError::EditorCallError { .. }
+            | OpenEditorError::EditorNotFound { .. }
+            | OpenEditorError::NoEditorFound { .. }
+            | OpenEditorError::NoUsableEditor { .. }
+            | OpenEditorError::EditAborted { .. } => None,
+            OpenEditorError::CommandFail { call: _, error }
+            | OpenEditorError::FileManipulationFail {
+                file_path: _,
+                error,
             }
+            | OpenEditorError::TempFileCleanupFail {
+                file_path: _,
+                error,
+            } => Some(error),
             OpenEditorError::EditorNotExecutable {
                 binary_path: _,
+                call: _,
                 error,
             } => error.as_ref().map(|e| e as &dyn std::error::Error),
-            OpenEditorError::TempFileCleanupFail(_) => None,
+            OpenEditorError::InvalidEditorCommand { command: _, error } => {
+                error.as_ref().map(|e| e as &dyn std::error::Error)
+            }
+            OpenEditorError::InvalidConfig {
+                file_path: _,
+                error,
+            }
+            | OpenEditorError::SerializationFail { error } => Some(error.as_ref()),
         }
     }
 }
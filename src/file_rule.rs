@@ -0,0 +1,141 @@
+use std::path::Path;
+
+/// An editor command to use for the files matching a glob pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileRule {
+    pattern: String,
+    command: String,
+}
+
+impl FileRule {
+    /// Creates a rule using `command` for the files matching `pattern`.
+    pub(crate) fn new(pattern: &str, command: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            command: command.to_string(),
+        }
+    }
+    /// Parses rules written as `pattern=command` and separated by `;`, like
+    /// `*.md=typora;*.log=less`. Invalid entries are ignored.
+    pub(crate) fn parse_list(rules: &str) -> Vec<Self> {
+        rules
+            .split(';')
+            .filter_map(|rule| rule.split_once('='))
+            .map(|(pattern, command)| (pattern.trim(), command.trim()))
+            .filter(|(pattern, command)| !pattern.is_empty() && !command.is_empty())
+            .map(|(pattern, command)| Self::new(pattern, command))
+            .collect()
+    }
+    /// Gets the editor command of the rule.
+    pub(crate) fn command(&self) -> &str {
+        &self.command
+    }
+    /// Whether the rule applies to `file_path`.
+    ///
+    /// Patterns containing a `/` are matched against the whole path, others against the file
+    /// name only. `*` matches any sequence of characters, `?` any single character, and case
+    /// is ignored for ASCII letters.
+    pub(crate) fn matches(&self, file_path: &Path) -> bool {
+        let target = if self.pattern.contains('/') {
+            file_path.to_str()
+        } else {
+            file_path.file_name().and_then(|name| name.to_str())
+        };
+        target.is_some_and(|target| {
+            let pattern = self.pattern.chars().collect::<Vec<_>>();
+            let target = target.chars().collect::<Vec<_>>();
+            glob_match(&pattern, &target)
+        })
+    }
+}
+
+/// Matches `text` against a glob `pattern` supporting `*` and `?`.
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern and of the text it was matched at,
+    // to backtrack when the rest of the pattern does not match
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c.eq_ignore_ascii_case(&text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    // Let the `*` match one more character
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        FileRule::new(pattern, "editor").matches(Path::new(path))
+    }
+
+    #[test]
+    fn star_and_question_mark() {
+        assert!(matches("*.md", "README.md"));
+        assert!(matches("*", "anything"));
+        assert!(matches("file.?s", "file.rs"));
+        assert!(!matches("file.?s", "file.s"));
+        assert!(!matches("*.md", "README.mdx"));
+        assert!(!matches("", "a"));
+    }
+
+    #[test]
+    fn star_backtracks() {
+        assert!(matches("*a*b", "xaxaxb"));
+        assert!(matches("a*b*c", "abbbcbc"));
+        assert!(!matches("a*b*c", "abbbcb"));
+        assert!(matches("*.tar.*", "archive.tar.tar.gz"));
+        assert!(matches("**x", "yyx"));
+    }
+
+    #[test]
+    fn patterns_with_slash_match_the_whole_path() {
+        assert!(matches("*.md", "/home/me/notes/todo.md"));
+        assert!(matches("/home/*/notes/*", "/home/me/notes/todo.md"));
+        assert!(!matches("notes/*", "/home/me/notes/todo.md"));
+        assert!(matches("*/notes/*", "/home/me/notes/todo.md"));
+        // Without a slash, only the file name is matched
+        assert!(!matches("notes*", "/home/me/notes/todo.md"));
+    }
+
+    #[test]
+    fn ascii_case_is_ignored() {
+        assert!(matches("*.MD", "readme.md"));
+        assert!(matches("makefile", "Makefile"));
+        assert!(!matches("é.txt", "É.txt"));
+    }
+
+    #[test]
+    fn parse_list_ignores_invalid_entries() {
+        let rules = FileRule::parse_list(
+            " *.md = typora ;;=vim;*.txt=;no-equal-sign;*.log=less;*.cfg=vim -c 'set ft=dosini'",
+        );
+        assert_eq!(
+            rules,
+            [
+                FileRule::new("*.md", "typora"),
+                FileRule::new("*.log", "less"),
+                FileRule::new("*.cfg", "vim -c 'set ft=dosini'"),
+            ]
+        );
+        assert!(FileRule::parse_list("").is_empty());
+    }
+}
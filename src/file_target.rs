@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+/// A file to open with [`EditorCallBuilder::open_files`](crate::EditorCallBuilder::open_files),
+/// optionally at a given position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTarget {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Line to open the file at, starting at 1.
+    pub line: Option<usize>,
+    /// Column to open the file at, starting at 1.
+    pub column: Option<usize>,
+}
+impl FileTarget {
+    /// Creates a new [`FileTarget`] with no position.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+            column: None,
+        }
+    }
+    /// Sets the line to open the file at.
+    #[must_use]
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+    /// Sets the column to open the file at.
+    #[must_use]
+    pub fn at_column(self, column: usize) -> Self {
+        Self {
+            column: Some(column),
+            ..self
+        }
+    }
+    /// Gets the line and column, defaulting to 1, or `None` if neither is set.
+    #[must_use]
+    pub fn position(&self) -> Option<(usize, usize)> {
+        (self.line.is_some() || self.column.is_some())
+            .then(|| (self.line.unwrap_or(1), self.column.unwrap_or(1)))
+    }
+}
+impl From<&Path> for FileTarget {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+impl From<PathBuf> for FileTarget {
+    fn from(path: PathBuf) -> Self {
+        Self::new(path)
+    }
+}
@@ -0,0 +1,208 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{errors::OpenEditorError, validation::ValidationError};
+
+/// A format in which values are edited, see [`EditorCallBuilder::edit_value`](crate::EditorCallBuilder::edit_value).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Toml,
+    Json,
+    Yaml,
+    Ron,
+}
+
+impl Format {
+    /// Gets the file extension of the format, including the dot.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Toml => ".toml",
+            Format::Json => ".json",
+            Format::Yaml => ".yaml",
+            Format::Ron => ".ron",
+        }
+    }
+    /// Gets the prefix of comment lines. JSON has no comments, but errors are shown as
+    /// `//` lines that are removed before parsing.
+    #[must_use]
+    pub fn comment_prefix(self) -> &'static str {
+        match self {
+            Format::Toml | Format::Yaml => "#",
+            Format::Json | Format::Ron => "//",
+        }
+    }
+    /// Serializes `value` in this format.
+    pub(crate) fn serialize<T: Serialize>(self, value: &T) -> Result<String, OpenEditorError> {
+        let result = match self {
+            Format::Toml => toml::to_string_pretty(value).map_err(Into::into),
+            Format::Json => serde_json::to_string_pretty(value)
+                .map(|json| json + "\n")
+                .map_err(Into::into),
+            Format::Yaml => serde_yaml_ng::to_string(value).map_err(Into::into),
+            Format::Ron => ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+                .map(|ron| ron + "\n")
+                .map_err(Into::into),
+        };
+        result.map_err(|error| OpenEditorError::SerializationFail { error })
+    }
+    /// Deserializes a value from `text` in this format, with the position of the error if any.
+    pub(crate) fn deserialize<T: DeserializeOwned>(self, text: &str) -> Result<T, ValidationError> {
+        match self {
+            Format::Toml => toml::from_str(text).map_err(|e| {
+                let error = ValidationError::new(e.message());
+                match e.span() {
+                    Some(span) => at_offset(error, text, span.start),
+                    None => error,
+                }
+            }),
+            Format::Json => serde_json::from_str(text).map_err(|e| {
+                let error = ValidationError::new(without_position(&e.to_string()));
+                // Columns are counted in bytes
+                let line_start = text
+                    .split_inclusive('\n')
+                    .take(e.line().saturating_sub(1))
+                    .map(str::len)
+                    .sum::<usize>();
+                at_offset(error, text, line_start + e.column().saturating_sub(1))
+            }),
+            Format::Yaml => serde_yaml_ng::from_str(text).map_err(|e| {
+                let error = ValidationError::new(without_position(&e.to_string()));
+                match e.location() {
+                    Some(location) => at_offset(error, text, location.index()),
+                    None => error,
+                }
+            }),
+            Format::Ron => ron::from_str(text).map_err(|e| {
+                ValidationError::new(e.code.to_string())
+                    .at_line(e.span.start.line)
+                    .at_column(e.span.start.col)
+            }),
+        }
+    }
+}
+
+/// Sets the position of `error` to the one of the byte `offset` in `text`.
+fn at_offset(error: ValidationError, text: &str, offset: usize) -> ValidationError {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    error.at_line(line).at_column(column)
+}
+
+/// Removes the ` at line L column C` suffix parsers add to their messages.
+fn without_position(message: &str) -> &str {
+    match message.rsplit_once(" at line ") {
+        Some((message, position))
+            if position
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_alphabetic() || c == ' ') =>
+        {
+            message
+        }
+        _ => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        name: String,
+        count: u32,
+    }
+
+    fn position(format: Format, text: &str) -> (Option<usize>, Option<usize>) {
+        let error = format.deserialize::<Settings>(text).unwrap_err();
+        assert!(!error.message.contains(" at line "), "{}", error.message);
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn toml_positions() {
+        // Spans are byte offsets
+        assert_eq!(
+            position(Format::Toml, "name = \"é€\"\ncount = 1 2\n"),
+            (Some(2), Some(9))
+        );
+        assert_eq!(
+            position(Format::Toml, "name = \"é€\" x\n"),
+            (Some(1), Some(13))
+        );
+    }
+
+    #[test]
+    fn json_positions() {
+        // Columns are counted in bytes by serde_json
+        assert_eq!(
+            position(Format::Json, "{\n  \"name\": \"é€\", \"count\": 1 2\n}\n"),
+            (Some(2), Some(28))
+        );
+        assert_eq!(
+            position(Format::Json, "{\n  \"name\": \"é€\", \"count\": \"x\"\n}\n"),
+            (Some(2), Some(28))
+        );
+    }
+
+    #[test]
+    fn yaml_positions() {
+        // Locations are byte offsets
+        assert_eq!(
+            position(Format::Yaml, "name: é€\ncount: x\n"),
+            (Some(2), Some(8))
+        );
+        assert_eq!(
+            position(Format::Yaml, "{name: é€, count: x}\n"),
+            (Some(1), Some(19))
+        );
+    }
+
+    #[test]
+    fn ron_positions() {
+        // Columns are counted in characters by ron
+        assert_eq!(
+            position(Format::Ron, "(\n  name: \"é€\", count: 1 2,\n)\n"),
+            (Some(2), Some(24))
+        );
+        assert_eq!(
+            position(Format::Ron, "(name: \"é€\", count: 1 2)\n"),
+            (Some(1), Some(23))
+        );
+    }
+
+    #[test]
+    fn offsets_inside_characters_are_moved_to_their_start() {
+        let error = at_offset(ValidationError::new("error"), "aé\nb", 2);
+        assert_eq!((error.line, error.column), (Some(1), Some(2)));
+        let error = at_offset(ValidationError::new("error"), "aé\nb", 10);
+        assert_eq!((error.line, error.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn positions_are_removed_from_messages() {
+        assert_eq!(
+            without_position("expected `,` or `}` at line 2 column 28"),
+            "expected `,` or `}`"
+        );
+        assert_eq!(
+            without_position("name: invalid type at line 1 column 7"),
+            "name: invalid type"
+        );
+        assert_eq!(
+            without_position("missing field `count`"),
+            "missing field `count`"
+        );
+    }
+}
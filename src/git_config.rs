@@ -0,0 +1,287 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Reads the value of `core.editor` from the git configuration, without running git.
+///
+/// The system, global and repository configuration files are read in this order, as well as
+/// the `GIT_CONFIG_COUNT` environment variables, and the last value found wins.
+/// `include` and `includeIf` directives are not followed.
+pub(crate) fn core_editor() -> Option<String> {
+    env_config_value("core.editor").or_else(|| find_in_files(&config_files(), "core", "editor"))
+}
+
+/// Finds the value of `section.key` in the last of `files` that sets it.
+fn find_in_files(files: &[PathBuf], section: &str, key: &str) -> Option<String> {
+    let mut value = None;
+    for file in files {
+        if let Ok(content) = std::fs::read_to_string(file) {
+            value = find_value(&content, section, key).or(value);
+        }
+    }
+    value
+}
+
+/// Lists the configuration files git reads, from lowest to highest priority.
+fn config_files() -> Vec<PathBuf> {
+    let mut files = vec![];
+    if !env_flag("GIT_CONFIG_NOSYSTEM") {
+        files.push(
+            env::var_os("GIT_CONFIG_SYSTEM").map_or_else(|| "/etc/gitconfig".into(), PathBuf::from),
+        );
+    }
+    if let Some(global) = env::var_os("GIT_CONFIG_GLOBAL") {
+        files.push(global.into());
+    } else {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+        let xdg_config = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".config")));
+        if let Some(xdg_config) = xdg_config {
+            files.push(xdg_config.join("git").join("config"));
+        }
+        if let Some(home) = home {
+            files.push(home.join(".gitconfig"));
+        }
+    }
+    if let Some(git_dir) = git_dir() {
+        // Worktrees share the configuration of the main repository
+        let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+            .map(|common_dir| git_dir.join(common_dir.trim()))
+            .unwrap_or(git_dir);
+        files.push(common_dir.join("config"));
+    }
+    files
+}
+
+/// Finds the git directory of the repository containing the current directory.
+fn git_dir() -> Option<PathBuf> {
+    if let Some(git_dir) = env::var_os("GIT_DIR") {
+        return Some(git_dir.into());
+    }
+    let current_dir = env::current_dir().ok()?;
+    current_dir.ancestors().find_map(|dir| {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            Some(dot_git)
+        } else if dot_git.is_file() {
+            // `.git` files of worktrees and submodules contain `gitdir: <path>`
+            let content = std::fs::read_to_string(&dot_git).ok()?;
+            let git_dir = Path::new(content.strip_prefix("gitdir:")?.trim());
+            Some(dir.join(git_dir))
+        } else {
+            None
+        }
+    })
+}
+
+/// Whether a boolean environment variable is set to a true value, as git understands it.
+fn env_flag(name: &str) -> bool {
+    env::var(name)
+        .is_ok_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+}
+
+/// Reads a configuration value given through `GIT_CONFIG_COUNT`, `GIT_CONFIG_KEY_<n>`
+/// and `GIT_CONFIG_VALUE_<n>`.
+fn env_config_value(key: &str) -> Option<String> {
+    let count: usize = env::var("GIT_CONFIG_COUNT").ok()?.parse().ok()?;
+    (0..count)
+        .filter(|i| {
+            env::var(format!("GIT_CONFIG_KEY_{i}")).is_ok_and(|k| k.eq_ignore_ascii_case(key))
+        })
+        .filter_map(|i| env::var(format!("GIT_CONFIG_VALUE_{i}")).ok())
+        .next_back()
+}
+
+/// Finds the last value of `section.key` in the content of a git configuration file.
+/// Section and key names are case-insensitive.
+fn find_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut value = None;
+    let mut in_section = false;
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let mut line = line.trim_start();
+        if let Some(header) = line.strip_prefix('[') {
+            let Some((name, rest)) = header.split_once(']') else {
+                continue;
+            };
+            in_section = name.trim().eq_ignore_ascii_case(section);
+            // A variable may follow the section header on the same line
+            line = rest.trim_start();
+        }
+        if !in_section || line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        let (name, raw_value) = line.split_once('=').unwrap_or((line, ""));
+        if !name.trim().eq_ignore_ascii_case(key) {
+            continue;
+        }
+        let mut raw_value = raw_value.to_string();
+        // A backslash at the end of the line continues the value on the next line
+        while raw_value.ends_with('\\') && !raw_value.ends_with("\\\\") {
+            raw_value.pop();
+            raw_value.push_str(lines.next().unwrap_or_default());
+        }
+        value = Some(parse_value(&raw_value));
+    }
+    value
+}
+
+/// Parses a raw configuration value: removes quotes, comments and surrounding whitespace,
+/// and interprets escape sequences.
+fn parse_value(raw_value: &str) -> String {
+    let mut value = String::new();
+    let mut in_quotes = false;
+    // Whitespace is only kept if something follows it or if it is quoted
+    let mut pending_whitespace = String::new();
+    let mut chars = raw_value.trim_start().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                value.push_str(&pending_whitespace);
+                pending_whitespace.clear();
+                in_quotes = !in_quotes;
+            }
+            '#' | ';' if !in_quotes => break,
+            '\\' => {
+                value.push_str(&pending_whitespace);
+                pending_whitespace.clear();
+                match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('b') => {
+                        value.pop();
+                    }
+                    Some(c) => value.push(c),
+                    None => {}
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => pending_whitespace.push(c),
+            c => {
+                value.push_str(&pending_whitespace);
+                pending_whitespace.clear();
+                value.push(c);
+            }
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(content: &str) -> Option<String> {
+        find_value(content, "core", "editor")
+    }
+
+    #[test]
+    fn plain_value() {
+        assert_eq!(editor("[core]\n\teditor = vim\n").as_deref(), Some("vim"));
+    }
+
+    #[test]
+    fn names_are_case_insensitive() {
+        assert_eq!(editor("[Core]\n  EDITOR=nano").as_deref(), Some("nano"));
+    }
+
+    #[test]
+    fn value_on_section_line() {
+        assert_eq!(editor("[core]editor = hx").as_deref(), Some("hx"));
+        assert_eq!(editor("[core] editor=hx # comment").as_deref(), Some("hx"));
+    }
+
+    #[test]
+    fn subsections_do_not_match() {
+        assert_eq!(editor("[core \"x\"]\n\teditor = vim\n"), None);
+        assert_eq!(editor("[user]\n\teditor = vim\n"), None);
+    }
+
+    #[test]
+    fn last_value_wins() {
+        let content = "[core]\n\teditor = vim\n[user]\n\tname = me\n[core]\n\teditor = nano\n";
+        assert_eq!(editor(content).as_deref(), Some("nano"));
+    }
+
+    #[test]
+    fn quotes_and_inline_comments() {
+        assert_eq!(
+            editor("[core]\n\teditor = \"code -w\" ; VS Code\n").as_deref(),
+            Some("code -w")
+        );
+        assert_eq!(
+            editor("[core]\n\teditor = emacsclient -a \"\" # server\n").as_deref(),
+            Some("emacsclient -a ")
+        );
+        assert_eq!(
+            editor("[core]\n\teditor = \"my editor # 1\"\n").as_deref(),
+            Some("my editor # 1")
+        );
+    }
+
+    #[test]
+    fn whitespace_is_kept_inside_values_only() {
+        assert_eq!(
+            editor("[core]\n\teditor =   vim  -u  NONE   \n").as_deref(),
+            Some("vim  -u  NONE")
+        );
+        assert_eq!(
+            editor("[core]\n\teditor = \" vim \"\n").as_deref(),
+            Some(" vim ")
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            editor(r#"[core] editor = "a\"b\\c\td\ne""#).as_deref(),
+            Some("a\"b\\c\td\ne")
+        );
+        assert_eq!(editor(r"[core] editor = abc\bd").as_deref(), Some("abd"));
+    }
+
+    #[test]
+    fn continuation_lines() {
+        assert_eq!(
+            editor("[core]\n\teditor = code \\\n-w\n\tpager = less\n").as_deref(),
+            Some("code -w")
+        );
+        // An escaped backslash at the end does not continue the value
+        assert_eq!(
+            editor("[core]\n\teditor = a\\\\\n\teditor2 = b\n").as_deref(),
+            Some("a\\")
+        );
+    }
+
+    #[test]
+    fn key_without_value() {
+        assert_eq!(editor("[core]\n\teditor\n").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn last_file_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+        let system = write("system", "[core]\n\teditor = vi\n");
+        let global = write("global", "[core]\n\teditor = nano\n");
+        let repo = write("repo", "[user]\n\tname = me\n");
+        let missing = dir.path().join("missing");
+        let files = [system.clone(), global, repo, missing];
+        assert_eq!(
+            find_in_files(&files, "core", "editor").as_deref(),
+            Some("nano")
+        );
+        assert_eq!(
+            find_in_files(&[files[1].clone(), system], "core", "editor").as_deref(),
+            Some("vi")
+        );
+    }
+}
@@ -1,17 +1,49 @@
+pub mod arg_template;
+mod backing_file;
+pub mod cleanup;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod edit_outcome;
 pub mod editor;
 pub mod editor_call_builder;
+pub mod editor_handle;
 pub mod editor_kind;
+pub mod editor_profile;
+pub mod environment;
 pub mod errors;
+mod file_rule;
+pub mod file_target;
+#[cfg(feature = "serde")]
+pub mod format;
+mod git_config;
+mod stderr_capture;
+pub mod terminal;
+pub mod validation;
 
 use std::path::Path;
 
+pub use arg_template::ArgTemplate;
+pub use cleanup::CleanupMode;
+#[cfg(feature = "config")]
+pub use config::Config;
+pub use edit_outcome::EditOutcome;
 pub use editor::Editor;
-pub use editor_call_builder::EditorCallBuilder;
-pub use editor_kind::EditorKind;
+pub use editor_call_builder::{EditorCallBuilder, EditorResolution, StderrMode};
+pub use editor_handle::EditorHandle;
+pub use editor_kind::{EditorKind, JetBrainsIde, NvimGui};
+pub use editor_profile::{EditorInterface, EditorProfile, WaitBehavior};
+pub use environment::Environment;
+pub use file_target::FileTarget;
+#[cfg(feature = "serde")]
+pub use format::Format;
+pub use terminal::{TerminalEmulator, TerminalKind};
+pub use validation::ValidationError;
 
 use crate::errors::OpenEditorError;
 
 static ENV_VARS: &[&str] = &["VISUAL", "EDITOR"];
+static FALLBACK_EDITORS: &[&str] = &["sensible-editor", "editor", "nano", "vim", "vi"];
+static FILE_EDITORS_ENV_VAR: &str = "OPEN_EDITOR_FILE_EDITORS";
 /// Macro to implement static methods for `EditorCallBuilder`.
 macro_rules! impl_static_editor_methods {
     (
@@ -28,6 +60,20 @@ macro_rules! impl_static_editor_methods {
             )*
 
     };
+    (
+        $(
+            $(#[$doc:meta])*
+            async $static_name:ident($($param:ident: $param_type:ty),*) -> $return_type:ty => $instance_method:ident
+        ),* $(,)?
+    ) => {
+            $(
+                $(#[$doc])*
+                pub async fn $static_name($($param: $param_type),*) -> $return_type {
+                    EditorCallBuilder::new().$instance_method($($param),*).await
+                }
+            )*
+
+    };
 }
 
 impl_static_editor_methods! {
@@ -62,4 +108,59 @@ impl_static_editor_methods! {
     /// # Errors
     /// Returns an error if the editor call fails or if the file cannot be read.
     open_file(file_path: &Path) -> Result<(), OpenEditorError> => open_file,
+
+    /// Open several files in the default editor, in a single session when it supports it.
+    ///
+    /// This is a static convenience method equivalent to `EditorCallBuilder::new().open_files(files)`.
+    ///
+    /// # Errors
+    /// Returns an error if an editor call fails.
+    open_files(files: &[FileTarget]) -> Result<(), OpenEditorError> => open_files,
+}
+
+#[cfg(feature = "tokio")]
+impl_static_editor_methods! {
+    /// Asynchronous version of [`open_editor`].
+    ///
+    /// # Errors
+    /// Returns an error if the editor call fails, or if the temporary file cannot be read or cleaned up.
+    async open_editor_async() -> Result<String, OpenEditorError> => open_editor_async,
+
+    /// Asynchronous version of [`edit_string`].
+    ///
+    /// # Errors
+    /// Returns an error if the editor call fails, or if the temporary file cannot be read or cleaned up.
+    async edit_string_async(string: &str) -> Result<String, OpenEditorError> => edit_string_async,
+
+    /// Asynchronous version of [`edit_string_mut`].
+    ///
+    /// # Errors
+    /// Returns an error if the editor call fails, or if the temporary file cannot be read or cleaned up.
+    async edit_string_mut_async(string: &mut String) -> Result<(), OpenEditorError> => edit_string_mut_async,
+
+    /// Asynchronous version of [`open_file`].
+    ///
+    /// # Errors
+    /// Returns an error if the editor call fails.
+    async open_file_async(file_path: &Path) -> Result<(), OpenEditorError> => open_file_async,
+
+    /// Asynchronous version of [`open_files`].
+    ///
+    /// # Errors
+    /// Returns an error if an editor call fails.
+    async open_files_async(files: &[FileTarget]) -> Result<(), OpenEditorError> => open_files_async,
+}
+
+/// Edit a value serialized in `format` in the default editor and return the edited value.
+///
+/// This is a static convenience method equivalent to `EditorCallBuilder::new().edit_value(value, format)`.
+///
+/// # Errors
+/// Returns an error if the value cannot be serialized, if the editor call fails, or if the edit is aborted.
+#[cfg(feature = "serde")]
+pub fn edit_value<T: serde::Serialize + serde::de::DeserializeOwned>(
+    value: &T,
+    format: Format,
+) -> Result<T, OpenEditorError> {
+    EditorCallBuilder::new().edit_value(value, format)
 }
@@ -0,0 +1,117 @@
+use std::{
+    io::{Read, Write},
+    process::ChildStderr,
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver},
+    },
+    thread,
+    time::Duration,
+};
+
+/// How long to wait for the end of the editor stderr once it exited. Some editors fork
+/// processes that keep the stream open, so it is not read until the end.
+const STDERR_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+/// Reads the stderr of an editor in the background, optionally copying it to our own stderr.
+#[derive(Debug)]
+pub(crate) struct StderrCapture {
+    output: Arc<Mutex<Vec<u8>>>,
+    done: Receiver<()>,
+}
+
+impl StderrCapture {
+    /// Starts reading `stderr` in a background thread.
+    pub(crate) fn start(mut stderr: ChildStderr, tee: bool) -> Self {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let (sender, done) = mpsc::channel();
+        let thread_output = Arc::clone(&output);
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(n) = stderr.read(&mut buffer) {
+                if n == 0 {
+                    break;
+                }
+                if tee {
+                    let _ = std::io::stderr().write_all(&buffer[..n]);
+                }
+                if let Ok(mut output) = thread_output.lock() {
+                    output.extend_from_slice(&buffer[..n]);
+                }
+            }
+            let _ = sender.send(());
+        });
+        Self { output, done }
+    }
+    /// Returns what was captured, once the editor has exited.
+    pub(crate) fn finish(self) -> String {
+        let _ = self.done.recv_timeout(STDERR_GRACE_PERIOD);
+        self.output
+            .lock()
+            .map(|output| String::from_utf8_lossy(&output).into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Waits for an editor spawned with tokio while reading its stderr, optionally copying it
+/// to our own stderr.
+#[cfg(feature = "tokio")]
+pub(crate) async fn wait_capturing_stderr(
+    child: &mut tokio::process::Child,
+    mut stderr: tokio::process::ChildStderr,
+    tee: bool,
+) -> std::io::Result<(std::process::ExitStatus, String)> {
+    use tokio::io::AsyncReadExt;
+
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let (sender, done) = mpsc::channel();
+    let task_output = Arc::clone(&output);
+    tokio::spawn(async move {
+        let mut buffer = [0; 4096];
+        while let Ok(n) = stderr.read(&mut buffer).await {
+            if n == 0 {
+                break;
+            }
+            if tee {
+                let _ = std::io::stderr().write_all(&buffer[..n]);
+            }
+            if let Ok(mut output) = task_output.lock() {
+                output.extend_from_slice(&buffer[..n]);
+            }
+        }
+        let _ = sender.send(());
+    });
+    let status = child.wait().await?;
+    // The grace period is waited for on the blocking pool, as the runtime may have no timers
+    let capture = StderrCapture { output, done };
+    let output = tokio::task::spawn_blocking(move || capture.finish())
+        .await
+        .unwrap_or_default();
+    Ok((status, output))
+}
+
+#[cfg(all(test, unix, feature = "tokio"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn async_capture_runs_without_timers() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        let (status, stderr) = runtime.block_on(async {
+            let mut child = tokio::process::Command::new("sh")
+                .args(["-c", "echo captured >&2; exit 3"])
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+                .unwrap();
+            let stderr = child.stderr.take().unwrap();
+            wait_capturing_stderr(&mut child, stderr, false)
+                .await
+                .unwrap()
+        });
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(stderr, "captured\n");
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::{editor::Editor, errors::OpenEditorError};
+
+/// Terminal emulators that can run a terminal editor in a new window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalKind {
+    /// Debian's alternative pointing to the default terminal emulator, when it cannot be
+    /// resolved to one of the other kinds.
+    XTerminalEmulator,
+    Kitty,
+    Alacritty,
+    Foot,
+    WezTerm,
+    GnomeTerminal,
+    Konsole,
+    Xterm,
+    /// A terminal emulator assumed to run the command following `-e`, like xterm.
+    Unknown,
+}
+
+impl TerminalKind {
+    /// Terminal emulators looked for in the `PATH` when `TERMINAL` is not set, by priority.
+    const DETECTED: [TerminalKind; 8] = [
+        TerminalKind::XTerminalEmulator,
+        TerminalKind::Kitty,
+        TerminalKind::Alacritty,
+        TerminalKind::Foot,
+        TerminalKind::WezTerm,
+        TerminalKind::GnomeTerminal,
+        TerminalKind::Konsole,
+        TerminalKind::Xterm,
+    ];
+
+    /// Gets the name of the binary of the terminal emulator.
+    fn binary_name(self) -> Option<&'static str> {
+        match self {
+            TerminalKind::XTerminalEmulator => Some("x-terminal-emulator"),
+            TerminalKind::Kitty => Some("kitty"),
+            TerminalKind::Alacritty => Some("alacritty"),
+            TerminalKind::Foot => Some("foot"),
+            TerminalKind::WezTerm => Some("wezterm"),
+            TerminalKind::GnomeTerminal => Some("gnome-terminal"),
+            TerminalKind::Konsole => Some("konsole"),
+            TerminalKind::Xterm => Some("xterm"),
+            TerminalKind::Unknown => None,
+        }
+    }
+    /// Detects the kind of a terminal emulator from the file name of its binary, ignoring
+    /// extensions such as Debian's `gnome-terminal.wrapper`.
+    fn from_binary_name(binary_path: &Path) -> Self {
+        let name = binary_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.split_once('.').map_or(name, |(base, _)| base));
+        match name {
+            Some("uxterm" | "lxterm") => TerminalKind::Xterm,
+            name => Self::DETECTED
+                .into_iter()
+                .find(|kind| kind.binary_name() == name)
+                .unwrap_or(TerminalKind::Unknown),
+        }
+    }
+    /// Detects the kind of a terminal emulator from the path of its binary, following
+    /// symbolic links when the name is not enough, such as for `x-terminal-emulator`.
+    ///
+    /// Returns the binary of the emulator found through the links, as wrappers such as
+    /// Debian's `gnome-terminal.wrapper` do not accept the same arguments.
+    fn resolve(binary_path: &Path) -> (Self, PathBuf) {
+        let kind = Self::from_binary_name(binary_path);
+        if !matches!(
+            kind,
+            TerminalKind::XTerminalEmulator | TerminalKind::Unknown
+        ) {
+            return (kind, binary_path.to_path_buf());
+        }
+        let target_kind = std::fs::canonicalize(binary_path)
+            .map(|target| Self::from_binary_name(&target))
+            .unwrap_or(TerminalKind::Unknown);
+        match target_kind {
+            TerminalKind::XTerminalEmulator | TerminalKind::Unknown => {
+                (kind, binary_path.to_path_buf())
+            }
+            target_kind => {
+                let target_path = target_kind
+                    .binary_name()
+                    .and_then(|name| which::which(name).ok())
+                    .unwrap_or_else(|| binary_path.to_path_buf());
+                (target_kind, target_path)
+            }
+        }
+    }
+    /// Gets the arguments making the terminal emulator run the command that follows them,
+    /// and exit when it exits.
+    fn exec_args(self) -> &'static [&'static str] {
+        match self {
+            TerminalKind::Kitty | TerminalKind::Foot => &[],
+            TerminalKind::WezTerm => &["start", "--always-new-process", "--"],
+            // The default is to return as soon as the window is opened
+            TerminalKind::GnomeTerminal => &["--wait", "--"],
+            TerminalKind::Konsole => &["--nofork", "-e"],
+            TerminalKind::XTerminalEmulator
+            | TerminalKind::Alacritty
+            | TerminalKind::Xterm
+            | TerminalKind::Unknown => &["-e"],
+        }
+    }
+}
+
+/// A terminal emulator used to run terminal editors when there is no terminal to run them in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalEmulator {
+    pub(crate) kind: TerminalKind,
+    pub(crate) binary_path: PathBuf,
+    pub(crate) args: Vec<String>,
+}
+
+impl TerminalEmulator {
+    /// Creates a new `TerminalEmulator` of the specified kind and binary path.
+    #[must_use]
+    pub fn new(kind: TerminalKind, binary_path: PathBuf) -> Self {
+        Self {
+            kind,
+            binary_path,
+            args: vec![],
+        }
+    }
+    /// Creates a new `TerminalEmulator` from a command line such as `kitty --single-instance`.
+    /// The kind is determined from the name of the program.
+    ///
+    /// # Errors
+    /// Returns an error if the command cannot be split (e.g. unterminated quotes) or is empty.
+    pub fn from_command(command: &str) -> Result<Self, OpenEditorError> {
+        let Editor {
+            binary_path, args, ..
+        } = Editor::from_command(command)?;
+        let (kind, binary_path) = TerminalKind::resolve(&binary_path);
+        Ok(Self {
+            kind,
+            binary_path,
+            args,
+        })
+    }
+    /// Finds the terminal emulator of the user: the command in the `TERMINAL` environment
+    /// variable, or the first of `x-terminal-emulator`, `kitty`, `alacritty`, `foot`,
+    /// `wezterm`, `gnome-terminal`, `konsole` and `xterm` found in the `PATH`.
+    ///
+    /// `x-terminal-emulator` is only used if it links to one of the other emulators, whose
+    /// arguments are known.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        let from_env = env::var("TERMINAL")
+            .ok()
+            .filter(|command| !command.is_empty())
+            .and_then(|command| Self::from_command(&command).ok())
+            .filter(|terminal| terminal.binary_path.is_file());
+        from_env.or_else(|| {
+            TerminalKind::DETECTED.into_iter().find_map(|kind| {
+                let binary_path = which::which(kind.binary_name()?).ok()?;
+                match TerminalKind::resolve(&binary_path) {
+                    (TerminalKind::XTerminalEmulator | TerminalKind::Unknown, _) => None,
+                    (kind, binary_path) => Some(Self::new(kind, binary_path)),
+                }
+            })
+        })
+    }
+    /// Gets the kind of the terminal emulator.
+    #[must_use]
+    pub fn kind(&self) -> TerminalKind {
+        self.kind
+    }
+    /// Gets the arguments making the terminal emulator run `command` and wait for it.
+    pub(crate) fn wrap_args(&self, command: &[String]) -> Vec<String> {
+        let exec_args = self.kind.exec_args().iter().map(|&s| s.to_string());
+        self.args
+            .iter()
+            .cloned()
+            .chain(exec_args)
+            .chain(command.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrap(kind: TerminalKind) -> Vec<String> {
+        TerminalEmulator::new(kind, PathBuf::from("term"))
+            .wrap_args(&["vim".to_string(), "file.txt".to_string()])
+    }
+
+    #[test]
+    fn wrap_args_per_kind() {
+        let cases: [(TerminalKind, &[&str]); 9] = [
+            (TerminalKind::XTerminalEmulator, &["-e"]),
+            (TerminalKind::Kitty, &[]),
+            (TerminalKind::Alacritty, &["-e"]),
+            (TerminalKind::Foot, &[]),
+            (
+                TerminalKind::WezTerm,
+                &["start", "--always-new-process", "--"],
+            ),
+            (TerminalKind::GnomeTerminal, &["--wait", "--"]),
+            (TerminalKind::Konsole, &["--nofork", "-e"]),
+            (TerminalKind::Xterm, &["-e"]),
+            (TerminalKind::Unknown, &["-e"]),
+        ];
+        for (kind, exec_args) in cases {
+            assert_eq!(
+                wrap(kind),
+                [exec_args, &["vim", "file.txt"]].concat(),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn wrap_args_keep_terminal_args_first() {
+        let terminal = TerminalEmulator {
+            args: vec!["--single-instance".to_string()],
+            ..TerminalEmulator::new(TerminalKind::Kitty, PathBuf::from("kitty"))
+        };
+        assert_eq!(
+            terminal.wrap_args(&["nano".to_string()]),
+            ["--single-instance", "nano"]
+        );
+    }
+
+    #[test]
+    fn kind_from_binary_name() {
+        for (name, kind) in [
+            ("/usr/bin/kitty", TerminalKind::Kitty),
+            ("gnome-terminal.wrapper", TerminalKind::GnomeTerminal),
+            ("uxterm", TerminalKind::Xterm),
+            ("wezterm.exe", TerminalKind::WezTerm),
+            ("x-terminal-emulator", TerminalKind::XTerminalEmulator),
+            ("xfce4-terminal.wrapper", TerminalKind::Unknown),
+        ] {
+            assert_eq!(
+                TerminalKind::from_binary_name(Path::new(name)),
+                kind,
+                "{name}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn x_terminal_emulator_is_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let link = dir.path().join("x-terminal-emulator");
+        for (target, kind) in [
+            ("konsole", TerminalKind::Konsole),
+            ("gnome-terminal.wrapper", TerminalKind::GnomeTerminal),
+            ("xfce4-terminal.wrapper", TerminalKind::XTerminalEmulator),
+        ] {
+            let target = dir.path().join(target);
+            std::fs::write(&target, "").unwrap();
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+            assert_eq!(TerminalKind::resolve(&link).0, kind, "{}", target.display());
+        }
+    }
+}
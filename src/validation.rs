@@ -0,0 +1,150 @@
+use std::{any::Any, fmt::Display};
+
+use crate::cleanup::Cleanup;
+
+/// An error returned by the validation function of
+/// [`EditorCallBuilder::edit_until_valid`](crate::EditorCallBuilder::edit_until_valid),
+/// telling where the editor should be reopened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Description of the error.
+    pub message: String,
+    /// Line of the error, starting at 1.
+    pub line: Option<usize>,
+    /// Column of the error, starting at 1.
+    pub column: Option<usize>,
+}
+impl ValidationError {
+    /// Creates a new [`ValidationError`] with no position.
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+    /// Sets the line of the error.
+    #[must_use]
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+    /// Sets the column of the error.
+    #[must_use]
+    pub fn at_column(self, column: usize) -> Self {
+        Self {
+            column: Some(column),
+            ..self
+        }
+    }
+}
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{} (line {line}, column {column})", self.message)
+            }
+            (Some(line), None) => write!(f, "{} (line {line})", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+impl std::error::Error for ValidationError {}
+
+/// The comment block shown above the content after a validation error, and where to place the
+/// cursor.
+pub(crate) struct ErrorHeader {
+    pub(crate) text: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+impl ErrorHeader {
+    /// Describes `error` with comment lines, and places the cursor at its position if it is a
+    /// [`ValidationError`], or at the start of the content otherwise.
+    pub(crate) fn new<E: Display + 'static>(error: &E, cleanup: &Cleanup) -> Self {
+        let message = format!("Error: {error}");
+        let mut text = message
+            .lines()
+            .map(|line| cleanup.comment(line))
+            .collect::<String>();
+        text.push_str(&cleanup.comment("Fix it and save, or empty the file to abort."));
+        let header_lines = text.lines().count();
+        let position = (error as &dyn Any).downcast_ref::<ValidationError>();
+        Self {
+            line: header_lines + position.and_then(|e| e.line).unwrap_or(1),
+            column: position.and_then(|e| e.column).unwrap_or(1),
+            text,
+        }
+    }
+    /// Removes the header from the edited content. If the user changed it, only the leading
+    /// lines that are still lines of the header are removed, keeping the user's own comments.
+    pub(crate) fn strip<'a>(&self, content: &'a str) -> &'a str {
+        if let Some(text) = content.strip_prefix(&self.text) {
+            return text;
+        }
+        let mut text = content;
+        while let Some((line, rest)) = text.split_once('\n') {
+            let is_header_line = self
+                .text
+                .lines()
+                .any(|header_line| header_line.trim_end() == line.trim_end());
+            if !is_header_line {
+                break;
+            }
+            text = rest;
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> ErrorHeader {
+        let error = ValidationError::new("expected a value")
+            .at_line(2)
+            .at_column(4);
+        ErrorHeader::new(&error, &Cleanup::default())
+    }
+
+    #[test]
+    fn header_describes_the_error_and_places_the_cursor() {
+        let header = header();
+        assert_eq!(
+            header.text,
+            "# Error: expected a value (line 2, column 4)\n\
+             # Fix it and save, or empty the file to abort.\n"
+        );
+        assert_eq!((header.line, header.column), (4, 4));
+        let header = ErrorHeader::new(&"first\nsecond", &Cleanup::default());
+        assert_eq!(header.text.lines().count(), 3);
+        assert_eq!((header.line, header.column), (4, 1));
+    }
+
+    #[test]
+    fn unchanged_header_is_removed() {
+        let header = header();
+        let content = format!("{}# user comment\nkey = 1\n", header.text);
+        assert_eq!(header.strip(&content), "# user comment\nkey = 1\n");
+    }
+
+    #[test]
+    fn edited_header_keeps_user_comments() {
+        let header = header();
+        let content = "# Fix it and save, or empty the file to abort.  \n\
+                       # user comment\n\
+                       key = 1\n";
+        assert_eq!(header.strip(content), "# user comment\nkey = 1\n");
+        // An edited header line is left to the validation function
+        let content = "# Error: expected a value!\n\
+                       # Fix it and save, or empty the file to abort.\n\
+                       key = 1\n";
+        assert_eq!(header.strip(content), content);
+        let content = "# user comment\n# another one\nkey = 1\n";
+        assert_eq!(header.strip(content), content);
+    }
+}